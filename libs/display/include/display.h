@@ -8,27 +8,401 @@ typedef enum {
     NULL_POINTER,
     INVALID_DISPLAY_PARAMS,
     FAILED_TO_DRAW,
+    CLIP_STACK_FULL,
+    CLIP_STACK_EMPTY,
+    BUFFER_TOO_SMALL,
+    OUT_OF_BOUNDS,
+    NO_FRAMEBUFFER,
+    OUT_OF_MEMORY,
+    MALFORMED_DATA,
 } fb_error;
 
+typedef enum {
+    PIXEL_XRGB8888 = 0,  // 0x00RRGGBB
+    PIXEL_RGB565,
+    PIXEL_RGBX8888,      // 0xRRGGBB00
+    PIXEL_XBGR8888,      // 0x00BBGGRR
+} pixel_format;
+
+typedef struct {
+    uint8_t red_shift,   red_size;
+    uint8_t green_shift, green_size;
+    uint8_t blue_shift,  blue_size;
+} channel_layout_t;
+
+typedef enum {
+    BLEND_NORMAL = 0,
+    BLEND_MULTIPLY,
+    BLEND_SCREEN,
+    BLEND_ADD,
+    BLEND_SUBTRACT,
+} blend_mode;
+
+typedef enum {
+    ARROW_UP = 0,
+    ARROW_DOWN,
+    ARROW_LEFT,
+    ARROW_RIGHT,
+} arrow_direction;
+
+typedef struct {
+    int32_t x;
+    int32_t y;
+} point_t;
+
+static inline point_t point_add(point_t a, point_t b) {
+    return (point_t){ a.x + b.x, a.y + b.y };
+}
+
+static inline point_t point_sub(point_t a, point_t b) {
+    return (point_t){ a.x - b.x, a.y - b.y };
+}
+
+static inline int64_t point_dot(point_t a, point_t b) {
+    return (int64_t)a.x * b.x + (int64_t)a.y * b.y;
+}
+
+static inline int64_t point_len_squared(point_t p) {
+    return point_dot(p, p);
+}
+
+// Affine map x' = a*x + b*y + tx, y' = c*x + d*y + ty. The matrix entries are
+// 16.16 fixed point; the translation is in whole pixels.
+typedef struct {
+    int32_t a, b;
+    int32_t c, d;
+    int32_t tx, ty;
+} transform_t;
+
+typedef struct {
+    uint32_t x;
+    uint32_t y;
+    uint32_t w;
+    uint32_t h;
+} rect_t;
+
+static inline int rect_is_empty(rect_t r) {
+    return r.w == 0 || r.h == 0;
+}
+
+static inline int rect_contains(rect_t r, uint32_t px, uint32_t py) {
+    return px >= r.x && py >= r.y && px - r.x < r.w && py - r.y < r.h;
+}
+
+// Store the overlap of a and b in *out; returns 0 (and leaves *out alone)
+// when they don't overlap.
+static inline int rect_intersect(rect_t a, rect_t b, rect_t *out) {
+    uint64_t x0 = a.x > b.x ? a.x : b.x, y0 = a.y > b.y ? a.y : b.y;
+    uint64_t ax1 = (uint64_t)a.x + a.w, bx1 = (uint64_t)b.x + b.w;
+    uint64_t ay1 = (uint64_t)a.y + a.h, by1 = (uint64_t)b.y + b.h;
+    uint64_t x1 = ax1 < bx1 ? ax1 : bx1, y1 = ay1 < by1 ? ay1 : by1;
+    if (x0 >= x1 || y0 >= y1) return 0;
+    if (out) *out = (rect_t){ (uint32_t)x0, (uint32_t)y0, (uint32_t)(x1 - x0), (uint32_t)(y1 - y0) };
+    return 1;
+}
+
+// Smallest rectangle covering both; an empty rectangle contributes nothing.
+static inline rect_t rect_union(rect_t a, rect_t b) {
+    if (rect_is_empty(a)) return b;
+    if (rect_is_empty(b)) return a;
+    uint64_t x0 = a.x < b.x ? a.x : b.x, y0 = a.y < b.y ? a.y : b.y;
+    uint64_t ax1 = (uint64_t)a.x + a.w, bx1 = (uint64_t)b.x + b.w;
+    uint64_t ay1 = (uint64_t)a.y + a.h, by1 = (uint64_t)b.y + b.h;
+    uint64_t x1 = ax1 > bx1 ? ax1 : bx1, y1 = ay1 > by1 ? ay1 : by1;
+    if (x1 > UINT32_MAX) x1 = UINT32_MAX;
+    if (y1 > UINT32_MAX) y1 = UINT32_MAX;
+    return (rect_t){ (uint32_t)x0, (uint32_t)y0, (uint32_t)(x1 - x0), (uint32_t)(y1 - y0) };
+}
+
+typedef struct {
+    uint32_t *buffer;
+    uint32_t  width;
+    uint32_t  height;
+    uint32_t  stride;
+} canvas_t;
+
+typedef struct {
+    canvas_t *canvas;
+    int32_t   x;
+    int32_t   y;
+    uint8_t   opacity;
+} layer_t;
+
+void     memset32(uint32_t *dst, uint32_t value, uint64_t count);
+
+const char *fb_error_str(fb_error err);
+
 fb_error init_display();
+uint32_t display_count();
+fb_error init_display_nth(uint32_t index);
+fb_error with_display(uint32_t index, void (*fn)(void *ctx), void *ctx);
+fb_error init_display_with(uint32_t *buffer, uint32_t width, uint32_t height, uint32_t pitch);
+fb_error init_display_format(uint32_t *buffer, uint32_t width, uint32_t height, uint32_t pitch, pixel_format format);
+fb_error init_back_buffer();
+fb_error set_channel_layout(const channel_layout_t *layout);
+channel_layout_t pixel_format_layout(pixel_format format);
+fb_error pixel_format_from_layout(uint32_t bpp, const channel_layout_t *layout, pixel_format *out);
+uint32_t convert_pixel(uint32_t color, pixel_format format);
+fb_error set_scale(uint32_t factor);
+void     set_antialias(int enable);
+uint8_t  set_global_alpha(uint8_t alpha);
+
+canvas_t *canvas_new(uint32_t width, uint32_t height);
+void      canvas_free(canvas_t *canvas);
+fb_error  bind_canvas(canvas_t *canvas);
+
+layer_t *layer_new(uint32_t width, uint32_t height);
+void     layer_free(layer_t *layer);
+fb_error composite_layer(const layer_t *layer);
+
+fb_error draw_cursor(int32_t x, int32_t y, uint32_t width, uint32_t height, const uint32_t *pixels);
+fb_error erase_cursor();
+
+fb_error swap_buffers();
+fb_error swap_region(uint32_t x, uint32_t y, uint32_t width, uint32_t height);
+fb_error swap_region_rect(rect_t r);
+fb_error present_diff(uint64_t *written);
+void     set_vsync_wait(void (*wait)(void));
+
+typedef struct {
+    uint64_t frames;
+    uint64_t last_swap_ticks;
+    uint64_t frame_ticks;
+} frame_stats_t;
+
+void          set_time_source(uint64_t (*now)(void));
+frame_stats_t frame_stats();
+
+void     enable_dirty_tracking(int enable);
+void     mark_dirty(uint32_t x, uint32_t y, uint32_t width, uint32_t height);
+void     mark_dirty_rect(rect_t r);
+void     clear_dirty();
+fb_error swap_dirty();
+
+fb_error push_clip(uint32_t x, uint32_t y, uint32_t width, uint32_t height);
+fb_error push_clip_rect(rect_t r);
+fb_error pop_clip();
+void     clip_bounds(uint32_t *x, uint32_t *y, uint32_t *width, uint32_t *height);
+rect_t   clip_rect();
+
+// Opaque named colours, CSS values.
+#define COLOR_BLACK   0xFF000000
+#define COLOR_WHITE   0xFFFFFFFF
+#define COLOR_RED     0xFFFF0000
+#define COLOR_GREEN   0xFF008000
+#define COLOR_LIME    0xFF00FF00
+#define COLOR_BLUE    0xFF0000FF
+#define COLOR_YELLOW  0xFFFFFF00
+#define COLOR_CYAN    0xFF00FFFF
+#define COLOR_MAGENTA 0xFFFF00FF
+#define COLOR_GRAY    0xFF808080
+#define COLOR_SILVER  0xFFC0C0C0
+#define COLOR_MAROON  0xFF800000
+#define COLOR_OLIVE   0xFF808000
+#define COLOR_NAVY    0xFF000080
+#define COLOR_PURPLE  0xFF800080
+#define COLOR_TEAL    0xFF008080
+#define COLOR_ORANGE  0xFFFFA500
+#define COLOR_PINK    0xFFFFC0CB
+#define COLOR_BROWN   0xFFA52A2A
+#define COLOR_GOLD    0xFFFFD700
+#define COLOR_INDIGO  0xFF4B0082
+#define COLOR_CORAL   0xFFFF7F50
+#define COLOR_SKYBLUE 0xFF87CEEB
+#define COLOR_SLATE   0xFF708090
 
 uint32_t color(uint8_t r, uint8_t g, uint8_t b);
+uint32_t rgba(uint8_t r, uint8_t g, uint8_t b, uint8_t a);
+uint16_t pack_rgb565(uint32_t color);
+uint32_t pack_color(uint8_t r, uint8_t g, uint8_t b);
+uint8_t  alpha_of(uint32_t color);
+uint8_t  red_of(uint32_t color);
+uint8_t  green_of(uint32_t color);
+uint8_t  blue_of(uint32_t color);
+uint32_t blend_colors(uint32_t bg, uint32_t fg, uint8_t alpha);
+uint32_t blend_colors_mode(uint32_t bg, uint32_t fg, uint8_t alpha, blend_mode mode);
+uint32_t darken(uint32_t color, uint8_t amount);
+uint32_t lighten(uint32_t color, uint8_t amount);
+uint32_t scale_color(uint32_t color, uint32_t num, uint32_t den);
+uint32_t mix(uint32_t a, uint32_t b, uint8_t t);
+int      color_from_hex(const char *s, uint32_t *out);
+int32_t  sin_deg(int32_t deg);
+int32_t  cos_deg(int32_t deg);
+transform_t transform_identity(void);
+transform_t transform_translate(int32_t dx, int32_t dy);
+transform_t transform_scale(int32_t sx, int32_t sy);
+transform_t transform_rotate(int32_t deg);
+transform_t transform_multiply(const transform_t *outer, const transform_t *inner);
+point_t     transform_apply(const transform_t *t, point_t p);
+uint32_t hsv(uint16_t h, uint8_t s, uint8_t v);
+void     rgb_to_hsv(uint32_t color, uint16_t *h, uint8_t *s, uint8_t *v);
 
 fb_error draw_pixel(uint32_t x, uint32_t y, uint32_t color);
+fb_error blend_pixel(uint32_t x, uint32_t y, uint32_t argb);
+fb_error draw_pixel_blended(uint32_t x, uint32_t y, uint32_t argb);
 uint32_t read_pixel(uint32_t x, uint32_t y);
+fb_error try_read_pixel(uint32_t x, uint32_t y, uint32_t *out);
+fb_error read_region(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t *out, uint64_t out_len, uint32_t *out_w, uint32_t *out_h);
 fb_error draw_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t color);
 fb_error draw_rect_outline(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t thickness, uint32_t color);
 fb_error draw_line(uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t color);
+fb_error draw_line_aa(uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t color);
+fb_error draw_line_thick(uint32_t x0, uint32_t y0, uint32_t x1, uint32_t y1, uint32_t color, uint32_t thickness);
 fb_error draw_circle(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t color);
 fb_error draw_circle_outline(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t color);
+fb_error draw_circle_aa(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t color);
+fb_error fill_circle_gradient(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t center, uint32_t edge);
+fb_error draw_circle_outline_aa(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t color);
+fb_error draw_spinner(uint32_t cx, uint32_t cy, uint32_t radius, uint8_t phase, uint32_t color);
+fb_error draw_ellipse(uint32_t cx, uint32_t cy, uint32_t rx, uint32_t ry, uint32_t color);
+fb_error fill_ellipse(uint32_t cx, uint32_t cy, uint32_t rx, uint32_t ry, uint32_t color);
+fb_error draw_arc(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t start_deg, uint32_t end_deg, uint32_t color);
+fb_error fill_pie(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t start_deg, uint32_t end_deg, uint32_t color);
+fb_error draw_bezier_quad(int32_t x0, int32_t y0, int32_t x1, int32_t y1, int32_t x2, int32_t y2, uint32_t color);
+fb_error draw_bezier_cubic(int32_t x0, int32_t y0, int32_t x1, int32_t y1, int32_t x2, int32_t y2, int32_t x3, int32_t y3, uint32_t color);
+fb_error draw_polyline(const point_t *points, uint32_t count, uint32_t color);
+fb_error draw_polygon(const point_t *points, uint32_t count, uint32_t color);
+fb_error fill_polygon(const point_t *points, uint32_t count, uint32_t color);
+fb_error fill_polygon_transformed(const point_t *points, uint32_t count, const transform_t *t, uint32_t color);
 fb_error draw_triangle(uint32_t x0, uint32_t y0, uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t color);
+fb_error fill_triangle(uint32_t x0, uint32_t y0, uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t color);
+fb_error draw_arrow(uint32_t cx, uint32_t cy, uint32_t size, arrow_direction dir, uint32_t color);
+fb_error draw_chevron(uint32_t cx, uint32_t cy, uint32_t size, arrow_direction dir, uint32_t color);
+fb_error fill_rounded_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t radius, uint32_t color);
+fb_error draw_bevel_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t fill, uint32_t light, uint32_t dark, uint32_t bevel);
+fb_error draw_rounded_rect_outline(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t radius, uint32_t thickness, uint32_t color);
+fb_error draw_shadow(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t offset, uint32_t blur);
+fb_error draw_inner_shadow(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t blur, uint32_t color);
+fb_error blur_region(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t radius);
+fb_error frosted_panel(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t tint, uint8_t tint_alpha);
 
+fb_error flood_fill(uint32_t x, uint32_t y, uint32_t color);
+fb_error fill_checker(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t cell, uint32_t color_a, uint32_t color_b);
+fb_error fill_dots(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t spacing, uint32_t color);
+fb_error scroll_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height, int32_t dx, int32_t dy, uint32_t fill);
 fb_error clear_screen(uint32_t color);
+fb_error clear_all(uint32_t color);
 fb_error draw_bitmap(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *bitmap);
+fb_error draw_sprite(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *pixels);
+fb_error draw_sprite_opaque(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *pixels);
+fb_error draw_sprite_scaled(uint32_t dst_x, uint32_t dst_y, uint32_t src_w, uint32_t src_h, const uint32_t *pixels, uint32_t dst_w, uint32_t dst_h);
+fb_error draw_sprite_scaled_bilinear(uint32_t dst_x, uint32_t dst_y, uint32_t src_w, uint32_t src_h, const uint32_t *pixels, uint32_t dst_w, uint32_t dst_h);
+fb_error draw_sprite_flipped(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *pixels, int flip_h, int flip_v);
+fb_error draw_sprite_rotated90(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *pixels, uint8_t quarter_turns);
+fb_error draw_sprite_rotated(int32_t cx, int32_t cy, uint32_t width, uint32_t height, const uint32_t *pixels, int32_t angle_deg);
+fb_error draw_nine_patch(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *pixels, uint32_t src_w, uint32_t src_h, uint32_t left, uint32_t right, uint32_t top, uint32_t bottom);
+fb_error draw_tiled(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint32_t *tile, uint32_t tile_w, uint32_t tile_h, int32_t off_x, int32_t off_y);
+fb_error draw_mask(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint8_t *bits, uint64_t len, uint32_t fg);
+fb_error draw_mask_bg(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint8_t *bits, uint64_t len, uint32_t fg, uint32_t bg);
+fb_error draw_indexed(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint8_t *indices, uint64_t len, const uint32_t *palette);
+fb_error palette_from(uint32_t *palette, const uint32_t *colors, uint32_t count);
+fb_error draw_rle(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const uint8_t *data, uint64_t len);
+
+typedef enum {
+    IMAGE_OK = 0,
+    IMAGE_TRUNCATED,
+    IMAGE_BAD_MAGIC,
+    IMAGE_BAD_HEADER,
+    IMAGE_UNSUPPORTED,
+    IMAGE_NO_MEMORY,
+    IMAGE_DRAW_FAILED,
+} image_error;
+
+typedef struct {
+    uint32_t  width;
+    uint32_t  height;
+    uint32_t *pixels;
+} image_t;
+
+image_error bmp_parse(image_t *img, const uint8_t *data, uint64_t len);
+image_error qoi_decode(image_t *img, const uint8_t *data, uint64_t len);
+image_error draw_qoi(uint32_t x, uint32_t y, const uint8_t *data, uint64_t len);
+void        image_free(image_t *img);
+image_error image_downscale(image_t *dst, const image_t *src, uint32_t dst_w, uint32_t dst_h);
+fb_error    draw_image(uint32_t x, uint32_t y, const image_t *img);
+
+typedef struct {
+    uint8_t  pos;
+    uint32_t color;
+} gradient_stop_t;
+
+fb_error draw_gradient_vertical(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t top, uint32_t bottom);
+fb_error draw_gradient_horizontal(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t left, uint32_t right);
+fb_error draw_gradient_vertical_srgb(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t top, uint32_t bottom);
+fb_error draw_gradient_horizontal_srgb(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t left, uint32_t right);
+fb_error draw_gradient_stops_vertical(uint32_t x, uint32_t y, uint32_t width, uint32_t height, const gradient_stop_t *stops, uint32_t count);
+
+#define FONT_WIDTH  8
+#define FONT_HEIGHT 8
+
+fb_error draw_char(uint32_t x, uint32_t y, char ch, uint32_t color);
+uint32_t draw_text(uint32_t x, uint32_t y, const char *s, uint32_t color);
+uint32_t draw_text_tracked(uint32_t x, uint32_t y, const char *s, uint32_t color, int32_t tracking);
+uint32_t draw_text_shadow(uint32_t x, uint32_t y, const char *s, uint32_t color, uint32_t shadow, uint32_t offset);
+void     measure_text_builtin(const char *s, uint32_t *width, uint32_t *height);
+fb_error draw_text_centered(rect_t r, const char *s, uint32_t color);
+fb_error draw_text_right(rect_t r, const char *s, uint32_t color);
+uint32_t draw_text_wrapped(uint32_t x, uint32_t y, uint32_t max_width, const char *s, uint32_t color);
+
+#define TEXT_ESC_MAX 16
+
+typedef struct {
+    uint32_t x;
+    uint32_t y;
+    uint32_t fg;
+    uint32_t bg;
+    uint32_t default_fg;
+    uint32_t default_bg;
+    uint8_t  esc_state;
+    uint8_t  esc_len;
+    char     esc_buf[TEXT_ESC_MAX];
+} text_cursor_t;
+
+void     text_cursor_init(text_cursor_t *tc, uint32_t x, uint32_t y, uint32_t fg, uint32_t bg);
+fb_error text_cursor_putc(text_cursor_t *tc, char ch);
+fb_error text_cursor_write(text_cursor_t *tc, const char *s);
+fb_error text_cursor_printf(text_cursor_t *tc, const char *fmt, ...);
+
+typedef enum {
+    FONT_OK = 0,
+    FONT_TRUNCATED,
+    FONT_BAD_MAGIC,
+    FONT_BAD_HEADER,
+    FONT_NO_MEMORY,
+    FONT_NULL_POINTER,
+} font_error;
+
+typedef struct {
+    uint8_t  width;
+    uint8_t  height;
+    int8_t   bearing_x;
+    int8_t   bearing_y;
+    uint8_t  advance;
+    uint32_t offset;
+} glyph_t;
+
+typedef struct {
+    uint32_t       num_glyphs;
+    uint32_t       height;
+    uint32_t       row_bytes;
+    const glyph_t *glyphs;
+    const uint8_t *bitmap;
+} font_t;
+
+font_error font_from_psf2(font_t *font, const uint8_t *data, uint32_t len);
+void       font_free(font_t *font);
+uint32_t   draw_text_font(uint32_t x, uint32_t y, const char *s, const font_t *font, uint32_t color);
+void       measure_text(const char *s, const font_t *font, uint32_t *width, uint32_t *height);
 
 uint32_t display_width(); 
 uint32_t display_height();
 uint32_t display_pitch();
 uint32_t *display_buffer();
+uint32_t *display_back_buffer();
+uint32_t display_stride();
+uint32_t *snapshot(uint64_t *len);
+fb_error restore(const uint32_t *pixels, uint64_t len);
+fb_error crossfade(const uint32_t *from, const uint32_t *to, uint64_t len, uint8_t t);
+pixel_format display_format();
 
 #endif
\ No newline at end of file
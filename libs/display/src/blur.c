@@ -0,0 +1,150 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <display.h>
+
+// Blur radius of frosted_panel(): enough to make text behind it unreadable.
+#define FROST_RADIUS 6
+
+// One box-filter pass along a line of n pixels spaced `step` apart, all four
+// ARGB channels at once. A running sum slides along the line, so the cost does
+// not depend on the radius; samples past either end are clamped to the edge.
+static void blur_line(const uint32_t *src, uint32_t *dst, uint32_t n, uint32_t step,
+                      uint32_t radius) {
+    uint32_t taps = 2 * radius + 1;
+    uint32_t sum[4] = { 0, 0, 0, 0 };
+    for (int64_t i = -(int64_t)radius; i <= (int64_t)radius; i++) {
+        uint32_t p = src[(i < 0 ? 0 : i >= n ? n - 1 : (uint32_t)i) * step];
+        for (int ch = 0; ch < 4; ch++) sum[ch] += (p >> (ch * 8)) & 0xFF;
+    }
+    for (uint32_t i = 0; i < n; i++) {
+        uint32_t out = 0;
+        for (int ch = 0; ch < 4; ch++)
+            out |= ((sum[ch] + taps / 2) / taps) << (ch * 8);
+        dst[i * step] = out;
+
+        int64_t add = (int64_t)i + radius + 1, sub = (int64_t)i - radius;
+        uint32_t pa = src[(add >= n ? n - 1 : (uint32_t)add) * step];
+        uint32_t ps = src[(sub < 0 ? 0 : (uint32_t)sub) * step];
+        for (int ch = 0; ch < 4; ch++)
+            sum[ch] += ((pa >> (ch * 8)) & 0xFF) - ((ps >> (ch * 8)) & 0xFF);
+    }
+}
+
+// Separable box blur of a packed width x height image in place: a horizontal
+// pass into scratch, then a vertical pass back.
+static fb_error box_blur(uint32_t *pixels, uint32_t width, uint32_t height, uint32_t radius) {
+    if (radius == 0 || width == 0 || height == 0) return OK;
+    uint32_t *tmp = malloc((uint64_t)width * height * sizeof(uint32_t));
+    if (!tmp) return NULL_POINTER;
+    for (uint32_t row = 0; row < height; row++)
+        blur_line(pixels + (uint64_t)row * width, tmp + (uint64_t)row * width, width, 1, radius);
+    for (uint32_t col = 0; col < width; col++)
+        blur_line(tmp + col, pixels + col, height, width, radius);
+    free(tmp);
+    return OK;
+}
+
+// Blur the visible part of a rectangle of the current target and mix `tint`
+// over the result at `tint_alpha` before writing it back.
+static fb_error blur_tinted(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                            uint32_t radius, uint32_t tint, uint8_t tint_alpha) {
+    rect_t r;
+    if ((radius == 0 && tint_alpha == 0) ||
+        !rect_intersect((rect_t){ x, y, width, height }, clip_rect(), &r))
+        return OK;
+
+    uint64_t len = (uint64_t)r.w * r.h;
+    uint32_t *pixels = malloc(len * sizeof(uint32_t));
+    if (!pixels) return NULL_POINTER;
+
+    uint32_t w, h;
+    fb_error err = read_region(r.x, r.y, r.w, r.h, pixels, len, &w, &h);
+    if (err == OK && radius) err = box_blur(pixels, w, h, radius);
+    if (err == OK && tint_alpha)
+        for (uint64_t i = 0; i < (uint64_t)w * h; i++)
+            pixels[i] = blend_colors(pixels[i], tint, tint_alpha);
+    if (err == OK) err = draw_bitmap(r.x, r.y, w, h, pixels);
+    free(pixels);
+    return err;
+}
+
+// Box-blur a rectangle of the current target, clipped to the clip rect.
+// Samples outside the rectangle are clamped to its edge.
+fb_error blur_region(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t radius) {
+    return blur_tinted(x, y, width, height, radius, 0, 0);
+}
+
+// Frosted glass: blur whatever is already behind the panel, then lay a
+// translucent tint over it.
+fb_error frosted_panel(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                       uint32_t tint, uint8_t tint_alpha) {
+    return blur_tinted(x, y, width, height, FROST_RADIUS, tint, tint_alpha);
+}
+
+// Soft drop shadow: the rectangle shifted by `offset`, drawn as a half-opaque
+// black mask that is box-blurred by `blur` pixels and then composited.
+// blur == 0 gives a hard-edged shadow.
+fb_error draw_shadow(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                     uint32_t offset, uint32_t blur) {
+    if (width == 0 || height == 0) return OK;
+    uint64_t mw = (uint64_t)width + 2 * (uint64_t)blur, mh = (uint64_t)height + 2 * (uint64_t)blur;
+    if (mw > UINT32_MAX || mh > UINT32_MAX) return INVALID_DISPLAY_PARAMS;
+
+    uint32_t *mask = malloc(mw * mh * sizeof(uint32_t));
+    if (!mask) return NULL_POINTER;
+    for (uint64_t row = 0; row < mh; row++) {
+        int inside_y = row >= blur && row < blur + (uint64_t)height;
+        for (uint64_t col = 0; col < mw; col++) {
+            int inside = inside_y && col >= blur && col < blur + (uint64_t)width;
+            mask[row * mw + col] = inside ? rgba(0, 0, 0, 128) : 0;
+        }
+    }
+
+    fb_error err = box_blur(mask, (uint32_t)mw, (uint32_t)mh, blur);
+    if (err != OK) {
+        free(mask);
+        return err;
+    }
+
+    int64_t ox = (int64_t)x + offset - blur, oy = (int64_t)y + offset - blur;
+    for (uint64_t row = 0; row < mh; row++) {
+        int64_t py = oy + (int64_t)row;
+        if (py < 0 || py > UINT32_MAX) continue;
+        for (uint64_t col = 0; col < mw; col++) {
+            int64_t px = ox + (int64_t)col;
+            if (px < 0 || px > UINT32_MAX) continue;
+            blend_pixel((uint32_t)px, (uint32_t)py, mask[row * mw + col]);
+        }
+    }
+    free(mask);
+    return OK;
+}
+
+// Recessed look: `color` is laid inside the rectangle along its edges, at the
+// colour's own alpha on the edge and fading linearly to nothing `blur` pixels
+// in. Each pixel takes its distance from the nearest edge, so corners get the
+// darkest of their two edges rather than a sum.
+fb_error draw_inner_shadow(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                           uint32_t blur, uint32_t color) {
+    if (blur == 0 || width == 0 || height == 0) return OK;
+    rect_t r;
+    if (!rect_intersect((rect_t){ x, y, width, height }, clip_rect(), &r)) return OK;
+
+    uint32_t alpha = alpha_of(color), rgb = color & 0x00FFFFFF;
+    for (uint32_t py = r.y; py < r.y + r.h; py++) {
+        uint32_t dy = py - y, dy2 = y + height - 1 - py;
+        if (dy2 < dy) dy = dy2;
+        for (uint32_t px = r.x; px < r.x + r.w; px++) {
+            uint32_t d = px - x, dx2 = x + width - 1 - px;
+            if (dx2 < d) d = dx2;
+            if (dy < d) d = dy;
+            if (d >= blur) {
+                // Nothing more to do in this row's middle; jump to the right band.
+                if (px < x + width - blur) px = x + width - blur - 1;
+                continue;
+            }
+            blend_pixel(px, py, ((alpha * (blur - d) / blur) << 24) | rgb);
+        }
+    }
+    return OK;
+}
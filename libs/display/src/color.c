@@ -0,0 +1,128 @@
+#include <stdint.h>
+#include <display.h>
+
+// Hue in degrees (wrapped to 0..359), saturation and value in 0..255.
+uint32_t hsv(uint16_t h, uint8_t s, uint8_t v) {
+    h %= 360;
+    if (s == 0) return color(v, v, v);
+
+    uint32_t region = h / 60;
+    uint32_t rem    = (uint32_t)(h % 60) * 255 / 60;
+    uint8_t p = (uint8_t)(v * (255 - s) / 255);
+    uint8_t q = (uint8_t)(v * (255 - s * rem / 255) / 255);
+    uint8_t t = (uint8_t)(v * (255 - s * (255 - rem) / 255) / 255);
+
+    switch (region) {
+        case 0:  return color(v, t, p);
+        case 1:  return color(q, v, p);
+        case 2:  return color(p, v, t);
+        case 3:  return color(p, q, v);
+        case 4:  return color(t, p, v);
+        default: return color(v, p, q);
+    }
+}
+
+void rgb_to_hsv(uint32_t c, uint16_t *h, uint8_t *s, uint8_t *v) {
+    int32_t r = red_of(c), g = green_of(c), b = blue_of(c);
+    int32_t max = r > g ? (r > b ? r : b) : (g > b ? g : b);
+    int32_t min = r < g ? (r < b ? r : b) : (g < b ? g : b);
+    int32_t delta = max - min;
+    int32_t hue = 0;
+
+    if (delta != 0) {
+        if (max == r)      hue = 60 * (g - b) / delta;
+        else if (max == g) hue = 120 + 60 * (b - r) / delta;
+        else               hue = 240 + 60 * (r - g) / delta;
+        if (hue < 0) hue += 360;
+    }
+
+    if (h) *h = (uint16_t)hue;
+    if (s) *s = max ? (uint8_t)(delta * 255 / max) : 0;
+    if (v) *v = (uint8_t)max;
+}
+
+static uint8_t blend_channel(uint32_t b, uint32_t f, blend_mode mode) {
+    int32_t v;
+    switch (mode) {
+        case BLEND_MULTIPLY: v = (int32_t)(b * f / 255); break;
+        case BLEND_SCREEN:   v = (int32_t)(255 - (255 - b) * (255 - f) / 255); break;
+        case BLEND_ADD:      v = (int32_t)(b + f); break;
+        case BLEND_SUBTRACT: v = (int32_t)b - (int32_t)f; break;
+        default:             v = (int32_t)f; break;
+    }
+    return (uint8_t)(v < 0 ? 0 : v > 255 ? 255 : v);
+}
+
+// Combine fg with bg channel by channel using `mode`, then mix that result
+// over bg by alpha. BLEND_NORMAL is plain blend_colors().
+uint32_t blend_colors_mode(uint32_t bg, uint32_t fg, uint8_t alpha, blend_mode mode) {
+    uint32_t mixed = color(blend_channel(red_of(bg),   red_of(fg),   mode),
+                           blend_channel(green_of(bg), green_of(fg), mode),
+                           blend_channel(blue_of(bg),  blue_of(fg),  mode));
+    return blend_colors(bg, mixed, alpha);
+}
+
+// Per-channel helpers. Alpha passes through unchanged except in mix(), which
+// interpolates it along with the colour.
+
+static inline uint8_t clamp_channel(uint64_t v) {
+    return (uint8_t)(v > 255 ? 255 : v);
+}
+
+// Move each channel `amount`/255 of the way towards black.
+uint32_t darken(uint32_t c, uint8_t amount) {
+    return scale_color(c, 255 - amount, 255);
+}
+
+// Move each channel `amount`/255 of the way towards white.
+uint32_t lighten(uint32_t c, uint8_t amount) {
+    uint32_t r = red_of(c), g = green_of(c), b = blue_of(c);
+    return rgba((uint8_t)(r + (255 - r) * amount / 255),
+                (uint8_t)(g + (255 - g) * amount / 255),
+                (uint8_t)(b + (255 - b) * amount / 255), alpha_of(c));
+}
+
+// Multiply each channel by num/den, saturating at 255. A zero denominator
+// leaves the colour as it is.
+uint32_t scale_color(uint32_t c, uint32_t num, uint32_t den) {
+    if (den == 0) return c;
+    return rgba(clamp_channel((uint64_t)red_of(c)   * num / den),
+                clamp_channel((uint64_t)green_of(c) * num / den),
+                clamp_channel((uint64_t)blue_of(c)  * num / den), alpha_of(c));
+}
+
+// Linear interpolation from a (t = 0) to b (t = 255), rounded.
+uint32_t mix(uint32_t a, uint32_t b, uint8_t t) {
+    uint32_t out = 0;
+    for (int shift = 0; shift < 32; shift += 8) {
+        int32_t ca = (a >> shift) & 0xFF, cb = (b >> shift) & 0xFF;
+        int32_t v  = ca + ((cb - ca) * t + (cb >= ca ? 127 : -127)) / 255;
+        out |= (uint32_t)v << shift;
+    }
+    return out;
+}
+
+static int hex_digit(char ch) {
+    if (ch >= '0' && ch <= '9') return ch - '0';
+    if (ch >= 'a' && ch <= 'f') return ch - 'a' + 10;
+    if (ch >= 'A' && ch <= 'F') return ch - 'A' + 10;
+    return -1;
+}
+
+// Parse "#RRGGBB" or the "#RGB" shorthand into an opaque colour. Returns 0 and
+// leaves *out alone if the string is anything else.
+int color_from_hex(const char *s, uint32_t *out) {
+    if (!s || s[0] != '#') return 0;
+    uint32_t len = 0;
+    while (s[1 + len] && len < 7) len++;
+    if (len != 3 && len != 6) return 0;
+
+    uint32_t v = 0;
+    for (uint32_t i = 0; i < len; i++) {
+        int d = hex_digit(s[1 + i]);
+        if (d < 0) return 0;
+        v = len == 3 ? (v << 8) | (uint32_t)(d * 0x11) : (v << 4) | (uint32_t)d;
+    }
+    if (out) *out = 0xFF000000 | v;
+    return 1;
+}
@@ -0,0 +1,201 @@
+#include <stdint.h>
+#include <stdarg.h>
+#include <stdio.h>
+#include <display.h>
+
+#define TAB_COLUMNS 8
+
+enum { ESC_NONE, ESC_START, ESC_CSI };
+
+// The eight basic ANSI colors, indexed by SGR code - 30 (or - 40).
+static const uint32_t ansi_colors[8] = {
+    0xFF000000, 0xFFAA0000, 0xFF00AA00, 0xFFAA5500,
+    0xFF0000AA, 0xFFAA00AA, 0xFF00AAAA, 0xFFAAAAAA,
+};
+
+// The console occupies the current clip rectangle, so pushing a clip before
+// writing confines it to a window; with no clip pushed it is the whole screen.
+typedef struct {
+    uint32_t left, top, right, bottom;
+} console_area_t;
+
+static console_area_t console_area(void) {
+    uint32_t x, y, w, h;
+    clip_bounds(&x, &y, &w, &h);
+    return (console_area_t){ x, y, x + w, y + h };
+}
+
+// Move to the start of the next line, scrolling the area up by one text row
+// when the cursor would fall off the bottom.
+static void newline(text_cursor_t *tc, const console_area_t *a) {
+    tc->x  = a->left;
+    tc->y += FONT_HEIGHT;
+    if (a->bottom - a->top < FONT_HEIGHT) return;
+    while (tc->y + FONT_HEIGHT > a->bottom) {
+        scroll_rect(a->left, a->top, a->right - a->left, a->bottom - a->top,
+                    0, -FONT_HEIGHT, tc->bg);
+        tc->y -= FONT_HEIGHT;
+    }
+}
+
+// Apply a buffered "ESC [ params m" sequence. Unknown parameters are skipped.
+static void apply_sgr(text_cursor_t *tc) {
+    uint32_t value = 0;
+    for (uint32_t i = 0; i <= tc->esc_len; i++) {
+        char ch = i < tc->esc_len ? tc->esc_buf[i] : ';';
+        if (ch >= '0' && ch <= '9') {
+            if (value < 1000) value = value * 10 + (uint32_t)(ch - '0');
+            continue;
+        }
+        if (ch != ';') return;
+        if (value == 0) {
+            tc->fg = tc->default_fg;
+            tc->bg = tc->default_bg;
+        } else if (value >= 30 && value <= 37) {
+            tc->fg = ansi_colors[value - 30];
+        } else if (value >= 40 && value <= 47) {
+            tc->bg = ansi_colors[value - 40];
+        }
+        value = 0;
+    }
+}
+
+// Feed one byte of an escape sequence. CSI sequences are buffered up to
+// their final byte; only SGR ("m") is acted on and everything else is
+// swallowed rather than printed.
+static void escape_char(text_cursor_t *tc, char ch) {
+    if (tc->esc_state == ESC_START) {
+        tc->esc_state = ch == '[' ? ESC_CSI : ESC_NONE;
+        tc->esc_len   = 0;
+        return;
+    }
+    if (ch >= 0x40 && ch <= 0x7E) {
+        if (ch == 'm' && tc->esc_len < TEXT_ESC_MAX) apply_sgr(tc);
+        tc->esc_state = ESC_NONE;
+        return;
+    }
+    if (tc->esc_len < TEXT_ESC_MAX) tc->esc_buf[tc->esc_len] = ch;
+    if (tc->esc_len < 255) tc->esc_len++;
+}
+
+static void put_char(text_cursor_t *tc, char ch, const console_area_t *a) {
+    if (tc->esc_state != ESC_NONE) {
+        escape_char(tc, ch);
+        return;
+    }
+    switch (ch) {
+    case '\x1b':
+        tc->esc_state = ESC_START;
+        return;
+    case '\n':
+        newline(tc, a);
+        return;
+    case '\r':
+        tc->x = a->left;
+        return;
+    case '\t': {
+        uint32_t col = (tc->x - a->left) / FONT_WIDTH;
+        tc->x = a->left + (col / TAB_COLUMNS + 1) * TAB_COLUMNS * FONT_WIDTH;
+        if (tc->x + FONT_WIDTH > a->right) newline(tc, a);
+        return;
+    }
+    case '\b':
+        if (tc->x < a->left + FONT_WIDTH) return;
+        tc->x -= FONT_WIDTH;
+        draw_rect(tc->x, tc->y, FONT_WIDTH, FONT_HEIGHT, tc->bg);
+        return;
+    }
+
+    if (tc->x + FONT_WIDTH > a->right) newline(tc, a);
+    draw_rect(tc->x, tc->y, FONT_WIDTH, FONT_HEIGHT, tc->bg);
+    draw_char(tc->x, tc->y, ch, tc->fg);
+    tc->x += FONT_WIDTH;
+}
+
+void text_cursor_init(text_cursor_t *tc, uint32_t x, uint32_t y, uint32_t fg, uint32_t bg) {
+    if (!tc) return;
+    tc->x         = x;
+    tc->y         = y;
+    tc->fg        = tc->default_fg = fg;
+    tc->bg        = tc->default_bg = bg;
+    tc->esc_state = ESC_NONE;
+    tc->esc_len   = 0;
+}
+
+fb_error text_cursor_putc(text_cursor_t *tc, char ch) {
+    if (!tc) return NULL_POINTER;
+    console_area_t a = console_area();
+    put_char(tc, ch, &a);
+    return OK;
+}
+
+// Write a string at the cursor, wrapping at the right edge of the clip and
+// scrolling it when the text runs past the bottom.
+fb_error text_cursor_write(text_cursor_t *tc, const char *s) {
+    if (!tc || !s) return NULL_POINTER;
+    console_area_t a = console_area();
+    for (; *s; s++)
+        put_char(tc, *s, &a);
+    return OK;
+}
+
+static void put_unsigned(text_cursor_t *tc, uint32_t v, uint32_t base, const console_area_t *a) {
+    char buf[11];
+    int  n = 0;
+    do {
+        uint32_t d = v % base;
+        buf[n++] = (char)(d < 10 ? '0' + d : 'a' + d - 10);
+        v /= base;
+    } while (v);
+    while (n) put_char(tc, buf[--n], a);
+}
+
+// Formatted output through the same glyph path as text_cursor_write().
+// Supports %d, %u, %x, %s, %c and %%, like the libc printf.
+fb_error text_cursor_printf(text_cursor_t *tc, const char *fmt, ...) {
+    if (!tc || !fmt) return NULL_POINTER;
+    console_area_t a = console_area();
+    va_list args;
+    va_start(args, fmt);
+    for (; *fmt; fmt++) {
+        if (*fmt != '%') {
+            put_char(tc, *fmt, &a);
+            continue;
+        }
+        fmt++;
+        switch (*fmt) {
+            case 'd': {
+                char buf[12];
+                itoa(va_arg(args, int), buf);
+                for (char *p = buf; *p; p++) put_char(tc, *p, &a);
+                break;
+            }
+            case 'u':
+                put_unsigned(tc, va_arg(args, uint32_t), 10, &a);
+                break;
+            case 'x':
+                put_unsigned(tc, va_arg(args, uint32_t), 16, &a);
+                break;
+            case 's': {
+                const char *s = va_arg(args, const char *);
+                for (; s && *s; s++) put_char(tc, *s, &a);
+                break;
+            }
+            case 'c':
+                put_char(tc, (char)va_arg(args, int), &a);
+                break;
+            case '%':
+                put_char(tc, '%', &a);
+                break;
+            case '\0':
+                va_end(args);
+                return OK;
+            default:
+                put_char(tc, '%', &a);
+                put_char(tc, *fmt, &a);
+                break;
+        }
+    }
+    va_end(args);
+    return OK;
+}
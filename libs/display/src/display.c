@@ -1,5 +1,7 @@
 #include <syscalls.h>
 #include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
 #include <display.h>
 
 uint32_t fb_width  = 0;
@@ -7,57 +9,756 @@ uint32_t fb_height = 0;
 uint32_t fb_pitch  = 0;
 uint32_t *fb_ptr   = 0;
 
+// Layout of the hardware framebuffer. Drawing always happens in 32-bit
+// 0xRRGGBB; any other format or channel order forces a back buffer that is
+// packed on swap. `fb_native` is set when no packing is needed at all.
+static pixel_format     fb_format = PIXEL_XRGB8888;
+static channel_layout_t fb_layout = { 16, 8, 8, 8, 0, 8 };
+static int              fb_native = 1;
+
+// Drawing goes to `target`: the framebuffer itself, the back buffer once
+// init_back_buffer() has been called, or a bound canvas. `stride` is the
+// target row length in pixels, which for the framebuffer is pitch / 4 rather
+// than the width, and surf_w x surf_h is the target's size.
+static uint32_t *back_buf = 0;
+static uint32_t *target   = 0;
+static uint32_t  stride   = 0;
+static uint32_t  surf_w   = 0, surf_h = 0;
+
+// With set_scale() the back buffer is the logical screen, back_w x back_h,
+// and each of its pixels is presented as a scale x scale block. scale_line is
+// one physical row of expanded pixels, reused while presenting.
+static uint32_t  scale  = 1;
+static uint32_t  back_w = 0, back_h = 0;
+static uint32_t *scale_line = 0;
+
+// What present_diff() last sent to the framebuffer. Allocated on first use and
+// invalidated by anything else that writes the framebuffer.
+static uint32_t *prev_frame = 0;
+static int       prev_valid = 0;
+
+// Frame counting for swap_buffers() and present_diff(). There is no clock in
+// here, so timing only happens once the caller supplies a time source.
+static frame_stats_t stats = { 0, 0, 0 };
+static uint64_t    (*time_source)(void) = 0;
+static int           timed = 0;
+
+// Called before each full-frame present so the kernel can hold off until
+// vertical blank, if it has a way to know when that is.
+static void        (*vsync_wait)(void) = 0;
+
+// When set, draw_line() and the circle functions take their anti-aliased
+// paths. Off by default: the aliased ones are cheaper and exact.
+static int antialias = 0;
+
+// Opacity applied on top of everything drawn, for fading whole elements in
+// and out. At 255 writes go straight through.
+static uint8_t global_alpha = 255;
+
+static void count_frame(void) {
+    stats.frames++;
+    if (!time_source) return;
+    uint64_t now = time_source();
+    if (timed) stats.frame_ticks = now - stats.last_swap_ticks;
+    stats.last_swap_ticks = now;
+    timed = 1;
+}
+
+// Writes are confined to [clip_x0, clip_x1) x [clip_y0, clip_y1): the screen,
+// narrowed by every rectangle currently on the clip stack. The edges are kept
+// unpacked because in_clip() sits on every pixel write.
+#define CLIP_STACK_DEPTH 16
+
+static rect_t   clip_stack[CLIP_STACK_DEPTH];
+static uint32_t clip_depth = 0, clip_base = 0;
+static uint32_t clip_x0 = 0, clip_y0 = 0, clip_x1 = 0, clip_y1 = 0;
+
+static inline int in_clip(int64_t x, int64_t y) {
+    return x >= clip_x0 && y >= clip_y0 && x < clip_x1 && y < clip_y1;
+}
+
+static inline void set_clip(rect_t r) {
+    clip_x0 = r.x;
+    clip_y0 = r.y;
+    clip_x1 = r.x + r.w;
+    clip_y1 = r.y + r.h;
+}
+
+// Everything drawn since the last swap_dirty(), as up to DIRTY_MAX separate
+// rectangles so that far-apart changes are presented separately. A new area
+// that touches a tracked one is merged into it; one more than fits marks the
+// whole screen dirty instead. Only maintained while dirty tracking is enabled.
+#define DIRTY_MAX 16
+
+static int      dirty_tracking = 0;
+static rect_t   dirty[DIRTY_MAX];
+static uint32_t dirty_count    = 0;
+static int      dirty_overflow = 0;
+
+static inline void dirty_reset(void) {
+    dirty_count    = 0;
+    dirty_overflow = 0;
+}
+
+static inline int rect_touches(rect_t a, rect_t b) {
+    return a.x <= (uint64_t)b.x + b.w && b.x <= (uint64_t)a.x + a.w &&
+           a.y <= (uint64_t)b.y + b.h && b.y <= (uint64_t)a.y + a.h;
+}
+
+static void dirty_merge(rect_t r) {
+    if (dirty_overflow || rect_is_empty(r)) return;
+    // The most recent rectangle is the likeliest to grow, e.g. along a span.
+    for (uint32_t i = dirty_count; i-- > 0; ) {
+        if (!rect_touches(dirty[i], r)) continue;
+        r = rect_union(dirty[i], r);
+        dirty[i] = dirty[--dirty_count];
+        i = dirty_count;
+    }
+    if (dirty_count == DIRTY_MAX) {
+        dirty_overflow = 1;
+        return;
+    }
+    dirty[dirty_count++] = r;
+}
+
+static inline void dirty_add(uint32_t x, uint32_t y, uint32_t x1, uint32_t y1) {
+    if (dirty_count && !dirty_overflow) {
+        rect_t *last = &dirty[dirty_count - 1];
+        if (x >= last->x && y >= last->y &&
+            x1 <= (uint64_t)last->x + last->w && y1 <= (uint64_t)last->y + last->h)
+            return;
+    }
+    dirty_merge((rect_t){ x, y, x1 - x, y1 - y });
+}
+
 static inline void put_pixel(uint32_t x, uint32_t y, uint32_t c) {
-    fb_ptr[y * (fb_pitch / 4) + x] = c;
+    uint32_t *p = &target[y * stride + x];
+    *p = global_alpha == 255 ? c : blend_colors(*p, c, global_alpha);
+    if (dirty_tracking) dirty_add(x, y, x + 1, y + 1);
+}
+
+// Fill n consecutive pixels of one row; no bounds checks, callers clip first.
+static inline void fill_row(uint32_t *line, uint32_t n, uint32_t c) {
+    memset32(line, c, n);
 }
 
 static inline uint32_t get_pixel(uint32_t x, uint32_t y) {
-    return fb_ptr[y * (fb_pitch / 4) + x];
+    return target[y * stride + x];
 }
 
 static inline int abs_i(int v) { return v < 0 ? -v : v; }
 
+// The screen's drawing state, parked here while a canvas is bound.
+static struct {
+    canvas_t   *bound;
+    uint32_t   *target;
+    uint32_t    stride, surf_w, surf_h;
+    rect_t      clip;
+    uint32_t    clip_depth, clip_base;
+    int         dirty_tracking;
+} screen;
+
+// Where each 8-bit channel of 0xRRGGBB lands in the formats with a name.
+static const channel_layout_t format_layouts[] = {
+    [PIXEL_XRGB8888] = { 16, 8,  8, 8,  0, 8 },
+    [PIXEL_RGB565]   = { 11, 5,  5, 6,  0, 5 },
+    [PIXEL_RGBX8888] = { 24, 8, 16, 8,  8, 8 },
+    [PIXEL_XBGR8888] = {  0, 8,  8, 8, 16, 8 },
+};
+
+#define FORMAT_COUNT (sizeof(format_layouts) / sizeof(format_layouts[0]))
+
+channel_layout_t pixel_format_layout(pixel_format format) {
+    return (uint32_t)format < FORMAT_COUNT ? format_layouts[format] : format_layouts[PIXEL_XRGB8888];
+}
+
+static inline uint32_t bytes_per_pixel(pixel_format format) {
+    return format == PIXEL_RGB565 ? 2 : 4;
+}
+
+static inline uint16_t *fb_row16(uint32_t y) {
+    return (uint16_t *)((uint8_t *)fb_ptr + (uint64_t)y * fb_pitch);
+}
+
+uint16_t pack_rgb565(uint32_t c) {
+    return (uint16_t)(((c >> 8) & 0xF800) | ((c >> 5) & 0x07E0) | ((c >> 3) & 0x001F));
+}
+
+static inline uint32_t pack_channel(uint8_t v, uint8_t shift, uint8_t size) {
+    return ((uint32_t)v >> (8 - size)) << shift;
+}
+
+static inline uint32_t pack_layout(uint32_t c, const channel_layout_t *l) {
+    return pack_channel(red_of(c),   l->red_shift,   l->red_size)   |
+           pack_channel(green_of(c), l->green_shift, l->green_size) |
+           pack_channel(blue_of(c),  l->blue_shift,  l->blue_size);
+}
+
+// Name the format a framebuffer's channel masks describe, e.g. as reported by
+// the bootloader. Returns INVALID_DISPLAY_PARAMS for layouts with no name;
+// those can still be driven through set_channel_layout().
+fb_error pixel_format_from_layout(uint32_t bpp, const channel_layout_t *layout, pixel_format *out) {
+    if (!layout || !out) return NULL_POINTER;
+    for (uint32_t f = 0; f < FORMAT_COUNT; f++) {
+        const channel_layout_t *l = &format_layouts[f];
+        if (bytes_per_pixel((pixel_format)f) * 8 != bpp) continue;
+        if (l->red_shift   == layout->red_shift   && l->red_size   == layout->red_size   &&
+            l->green_shift == layout->green_shift && l->green_size == layout->green_size &&
+            l->blue_shift  == layout->blue_shift  && l->blue_size  == layout->blue_size) {
+            *out = (pixel_format)f;
+            return OK;
+        }
+    }
+    return INVALID_DISPLAY_PARAMS;
+}
+
+// A 0xRRGGBB colour as the raw pixel value of `format`.
+uint32_t convert_pixel(uint32_t c, pixel_format format) {
+    channel_layout_t l = pixel_format_layout(format);
+    return pack_layout(c, &l);
+}
+
+static inline uint8_t unpack_channel(uint32_t p, uint8_t shift, uint8_t size) {
+    uint32_t max = (1u << size) - 1;
+    return (uint8_t)((((p >> shift) & max) * 255 + max / 2) / max);
+}
+
+// Shift 8-bit channels into the positions the hardware expects.
+uint32_t pack_color(uint8_t r, uint8_t g, uint8_t b) {
+    return pack_layout(color(r, g, b), &fb_layout);
+}
+
+static inline uint32_t unpack_color(uint32_t p) {
+    return color(unpack_channel(p, fb_layout.red_shift,   fb_layout.red_size),
+                 unpack_channel(p, fb_layout.green_shift, fb_layout.green_size),
+                 unpack_channel(p, fb_layout.blue_shift,  fb_layout.blue_size));
+}
+
+// Copy n pixels to the framebuffer at physical (x, y), packing them down to
+// the hardware pixel format on the way.
+static void store_span(uint32_t x, uint32_t y, const uint32_t *src, uint32_t w) {
+    if (fb_native) {
+        memcpy(fb_ptr + y * (fb_pitch / 4) + x, src, w * sizeof(uint32_t));
+        return;
+    }
+    if (fb_format == PIXEL_RGB565) {
+        uint16_t *dst = fb_row16(y) + x;
+        for (uint32_t i = 0; i < w; i++)
+            dst[i] = (uint16_t)pack_color(red_of(src[i]), green_of(src[i]), blue_of(src[i]));
+        return;
+    }
+    uint32_t *dst = fb_ptr + y * (fb_pitch / 4) + x;
+    for (uint32_t i = 0; i < w; i++)
+        dst[i] = pack_color(red_of(src[i]), green_of(src[i]), blue_of(src[i]));
+}
+
+// Present w pixels of back buffer row y starting at x. When scaled, the last
+// logical row and column also cover any physical remainder.
+static void present_row(uint32_t x, uint32_t y, uint32_t w) {
+    const uint32_t *src = back_buf + y * back_w + x;
+    if (scale == 1) {
+        store_span(x, y, src, w);
+        return;
+    }
+    uint32_t px0 = x * scale, px1 = x + w == back_w ? fb_width  : (x + w) * scale;
+    uint32_t py0 = y * scale, py1 = y + 1 == back_h ? fb_height : (y + 1) * scale;
+    for (uint32_t px = px0; px < px1; px++) {
+        uint32_t lx = px / scale;
+        scale_line[px] = back_buf[y * back_w + (lx < back_w ? lx : back_w - 1)];
+    }
+    for (uint32_t py = py0; py < py1; py++)
+        store_span(px0, py, scale_line + px0, px1 - px0);
+}
+
+typedef uint64_t __attribute__((may_alias)) pixel_pair_t;
+
+// Fill `count` 32-bit words with `value`, two at a time once the destination
+// is 8-byte aligned, with scalar stores for the head and tail.
+void memset32(uint32_t *dst, uint32_t value, uint64_t count) {
+    if (count && ((uintptr_t)dst & 7)) {
+        *dst++ = value;
+        count--;
+    }
+    pixel_pair_t  pair = ((uint64_t)value << 32) | value;
+    pixel_pair_t *wide = (pixel_pair_t *)dst;
+    for (uint64_t i = 0; i < count / 2; i++)
+        wide[i] = pair;
+    if (count & 1)
+        dst[count - 1] = value;
+}
+
+const char *fb_error_str(fb_error err) {
+    switch (err) {
+        case OK:                     return "ok";
+        case NULL_POINTER:           return "null pointer or allocation failure";
+        case INVALID_DISPLAY_PARAMS: return "invalid display parameters";
+        case FAILED_TO_DRAW:         return "failed to draw";
+        case CLIP_STACK_FULL:        return "clip stack full";
+        case CLIP_STACK_EMPTY:       return "clip stack empty";
+        case BUFFER_TOO_SMALL:       return "buffer too small";
+        case OUT_OF_BOUNDS:          return "out of bounds";
+        case NO_FRAMEBUFFER:         return "kernel reported no framebuffer";
+        case OUT_OF_MEMORY:          return "out of memory";
+        case MALFORMED_DATA:         return "malformed data";
+    }
+    return "unknown error";
+}
+
+// The kernel hands back a null pointer when the bootloader gave it no
+// framebuffer; report that distinctly rather than as a generic null pointer.
 fb_error init_display() {
-    fb_pitch  = get_fb_pitch();
-    fb_width  = get_fb_width();
-    fb_height = get_fb_height();
-    fb_ptr    = get_framebuffer();
-    if (!fb_ptr)
+    uint32_t *fb = get_framebuffer();
+    if (!fb) return NO_FRAMEBUFFER;
+    pixel_format format = get_fb_bpp() == 16 ? PIXEL_RGB565 : PIXEL_XRGB8888;
+    return init_display_format(fb, get_fb_width(), get_fb_height(), get_fb_pitch(), format);
+}
+
+// The framebuffer syscalls take no index and describe a single screen, so
+// there is exactly one display to enumerate until the kernel reports more.
+uint32_t display_count() {
+    return 1;
+}
+
+// init_display() for the display at `index`, counting from 0.
+fb_error init_display_nth(uint32_t index) {
+    if (index >= display_count()) return OUT_OF_BOUNDS;
+    return init_display();
+}
+
+// Run `fn` with the drawing functions aimed at the display at `index`. With a
+// single display they already are, so this only checks the index; `fn` is not
+// called for one that doesn't exist.
+fb_error with_display(uint32_t index, void (*fn)(void *ctx), void *ctx) {
+    if (!fn) return NULL_POINTER;
+    if (index >= display_count()) return OUT_OF_BOUNDS;
+    fn(ctx);
+    return OK;
+}
+
+// Point the display at any 32-bit buffer instead of the kernel framebuffer,
+// e.g. to render off-screen or to exercise the drawing code without hardware.
+// swap_buffers() then presents into that buffer.
+fb_error init_display_with(uint32_t *buffer, uint32_t width, uint32_t height, uint32_t pitch) {
+    return init_display_format(buffer, width, height, pitch, PIXEL_XRGB8888);
+}
+
+fb_error init_display_format(uint32_t *buffer, uint32_t width, uint32_t height, uint32_t pitch,
+                             pixel_format format) {
+    // Check everything before touching any state, so a rejected mode leaves
+    // the current one intact.
+    if (!buffer)
         return NULL_POINTER;
-    if (fb_pitch == 0 || fb_width == 0 || fb_height == 0)
+    if ((uint32_t)format >= FORMAT_COUNT) return INVALID_DISPLAY_PARAMS;
+    if (pitch == 0 || width == 0 || height == 0 ||
+        pitch / bytes_per_pixel(format) < width)
         return INVALID_DISPLAY_PARAMS;
-    for (uint32_t y = 0; y < fb_height; y++) {
-        for (uint32_t x = 0; x < fb_width; x++) {
-            put_pixel(x, y, 0);
+    fb_pitch  = pitch;
+    fb_width  = width;
+    fb_height = height;
+    fb_ptr    = buffer;
+    fb_format = format;
+    fb_layout = pixel_format_layout(format);
+    fb_native = format == PIXEL_XRGB8888;
+    if (back_buf) {
+        free(back_buf);
+        back_buf = 0;
+    }
+    if (prev_frame) {
+        free(prev_frame);
+        prev_frame = 0;
+    }
+    if (scale_line) {
+        free(scale_line);
+        scale_line = 0;
+    }
+    prev_valid = 0;
+    scale  = 1;
+    back_w = fb_width;
+    back_h = fb_height;
+    screen.bound = 0;
+    target = fb_ptr;
+    stride = fb_pitch / 4;
+    surf_w = fb_width;
+    surf_h = fb_height;
+    clip_depth = clip_base = 0;
+    clip_x0 = clip_y0 = 0;
+    clip_x1 = fb_width;
+    clip_y1 = fb_height;
+    if (!fb_native) {
+        for (uint32_t y = 0; y < fb_height; y++)
+            memset(fb_row16(y), 0, (int)(fb_width * bytes_per_pixel(fb_format)));
+        return init_back_buffer();
+    }
+    for (uint32_t y = 0; y < fb_height; y++)
+        fill_row(target + y * stride, fb_width, 0);
+    return OK;
+}
+
+fb_error init_back_buffer() {
+    if (!fb_ptr) return NULL_POINTER;
+    if (back_buf) return OK;
+    back_buf = malloc((uint64_t)back_w * back_h * sizeof(uint32_t));
+    if (!back_buf) return NULL_POINTER;
+    for (uint32_t row = 0; row < fb_height; row++) {
+        uint32_t *dst = back_buf + row * back_w;
+        if (fb_native) {
+            memcpy(dst, fb_ptr + row * (fb_pitch / 4), fb_width * sizeof(uint32_t));
+        } else if (fb_format == PIXEL_RGB565) {
+            const uint16_t *src = fb_row16(row);
+            for (uint32_t col = 0; col < fb_width; col++)
+                dst[col] = unpack_color(src[col]);
+        } else {
+            const uint32_t *src = fb_ptr + row * (fb_pitch / 4);
+            for (uint32_t col = 0; col < fb_width; col++)
+                dst[col] = unpack_color(src[col]);
         }
     }
+    if (screen.bound) {
+        screen.target = back_buf;
+        screen.stride = back_w;
+    } else {
+        target = back_buf;
+        stride = back_w;
+    }
     return OK;
 }
 
+// Describe where each channel lives in a hardware pixel. The kernel only
+// reports the bpp, so a BGR or otherwise unusual panel has to be set up here
+// after init; anything but plain 0xRRGGBB turns on the back buffer.
+fb_error set_channel_layout(const channel_layout_t *layout) {
+    if (!layout) return NULL_POINTER;
+    if (!fb_ptr) return INVALID_DISPLAY_PARAMS;
+    uint32_t bits = bytes_per_pixel(fb_format) * 8;
+    const uint8_t ch[3][2] = {
+        { layout->red_shift,   layout->red_size },
+        { layout->green_shift, layout->green_size },
+        { layout->blue_shift,  layout->blue_size },
+    };
+    for (int i = 0; i < 3; i++)
+        if (ch[i][1] == 0 || ch[i][1] > 8 || ch[i][0] + ch[i][1] > bits)
+            return INVALID_DISPLAY_PARAMS;
+
+    fb_layout = *layout;
+    fb_native = fb_format == PIXEL_XRGB8888 &&
+                layout->red_shift   == 16 && layout->red_size   == 8 &&
+                layout->green_shift == 8  && layout->green_size == 8 &&
+                layout->blue_shift  == 0  && layout->blue_size  == 8;
+    if (fb_native || back_buf) return OK;
+    return init_back_buffer();
+}
+
+// Render at 1/factor of the screen resolution: the back buffer shrinks to
+// the logical size, every drawing call works in logical pixels, and swaps
+// blow each pixel up to a factor x factor block. The back buffer is cleared.
+fb_error set_scale(uint32_t factor) {
+    if (!fb_ptr) return NULL_POINTER;
+    if (factor == 0 || factor > fb_width || factor > fb_height || screen.bound)
+        return INVALID_DISPLAY_PARAMS;
+
+    uint32_t w = fb_width / factor, h = fb_height / factor;
+    uint32_t *buf  = malloc((uint64_t)w * h * sizeof(uint32_t));
+    uint32_t *line = factor > 1 ? malloc((uint64_t)fb_width * sizeof(uint32_t)) : 0;
+    if (!buf || (factor > 1 && !line)) {
+        free(buf);
+        free(line);
+        return NULL_POINTER;
+    }
+    memset32(buf, 0, (uint64_t)w * h);
+
+    free(back_buf);
+    free(prev_frame);
+    free(scale_line);
+    back_buf   = buf;
+    scale_line = line;
+    prev_frame = 0;
+    prev_valid = 0;
+    scale  = factor;
+    back_w = w;
+    back_h = h;
+    target = back_buf;
+    stride = surf_w = w;
+    surf_h = h;
+    clip_depth = clip_base = 0;
+    set_clip((rect_t){ 0, 0, w, h });
+    dirty_reset();
+    return OK;
+}
+
+void set_antialias(int enable) {
+    antialias = enable != 0;
+}
+
+// Scale the opacity of everything drawn from now on by alpha / 255, on top of
+// any per-pixel alpha. Opaque primitives blend over what is there while it is
+// below 255. Returns the previous value so a caller can put it back.
+uint8_t set_global_alpha(uint8_t alpha) {
+    uint8_t old  = global_alpha;
+    global_alpha = alpha;
+    return old;
+}
+
+// Copy the back buffer to the screen one scanline at a time; the framebuffer
+// row length comes from the pitch, which is often wider than the visible width.
+fb_error swap_buffers() {
+    if (!back_buf) return NULL_POINTER;
+    if (vsync_wait) vsync_wait();
+    for (uint32_t row = 0; row < back_h; row++)
+        present_row(0, row, back_w);
+    prev_valid = 0;
+    count_frame();
+    return OK;
+}
+
+// Present only the given rectangle, clipped to the screen.
+fb_error swap_region(uint32_t x, uint32_t y, uint32_t width, uint32_t height) {
+    if (!back_buf) return NULL_POINTER;
+    if (x >= back_w || y >= back_h) return OK;
+    uint32_t w = (width  > back_w - x) ? back_w - x : width;
+    uint32_t h = (height > back_h - y) ? back_h - y : height;
+    for (uint32_t row = y; row < y + h; row++)
+        present_row(x, row, w);
+    prev_valid = 0;
+    return OK;
+}
+
+// Present only the pixels that differ from the previous present_diff(),
+// comparing against a shadow copy one 64-bit word (two pixels) at a time
+// wherever both rows are 8-byte aligned, and writing each changed span once. The first call, and the first after any other present,
+// sends the whole frame. The number of pixels written goes to *written.
+fb_error present_diff(uint64_t *written) {
+    if (written) *written = 0;
+    if (!back_buf) return NULL_POINTER;
+    if (!prev_frame) {
+        prev_frame = malloc((uint64_t)back_w * back_h * sizeof(uint32_t));
+        if (!prev_frame) return OUT_OF_MEMORY;
+        prev_valid = 0;
+    }
+    if (vsync_wait) vsync_wait();
+
+    uint64_t n = 0;
+    for (uint32_t row = 0; row < back_h; row++) {
+        const uint32_t *cur = back_buf + row * back_w;
+        uint32_t *old = prev_frame + row * back_w;
+        if (!prev_valid) {
+            present_row(0, row, back_w);
+            memcpy(old, cur, back_w * sizeof(uint32_t));
+            n += back_w;
+            continue;
+        }
+        uint32_t x = 0;
+        while (x < back_w) {
+            while (x < back_w) {
+                if (x + 1 < back_w && !(((uintptr_t)(cur + x) | (uintptr_t)(old + x)) & 7)
+                    && *(const pixel_pair_t *)(cur + x) == *(const pixel_pair_t *)(old + x)) {
+                    x += 2;
+                    continue;
+                }
+                if (cur[x] != old[x]) break;
+                x++;
+            }
+            if (x >= back_w) break;
+            uint32_t start = x;
+            while (x < back_w && cur[x] != old[x]) x++;
+            present_row(start, row, x - start);
+            memcpy(old + start, cur + start, (x - start) * sizeof(uint32_t));
+            n += x - start;
+        }
+    }
+    prev_valid = 1;
+    if (written) *written = n;
+    count_frame();
+    return OK;
+}
+
+// `wait` blocks until the next vertical blank; pass 0 to present immediately.
+void set_vsync_wait(void (*wait)(void)) {
+    vsync_wait = wait;
+}
+
+// `now` returns a monotonic tick count in whatever unit the caller likes;
+// frame_stats() reports in the same unit. Pass 0 to stop timing.
+void set_time_source(uint64_t (*now)(void)) {
+    time_source = now;
+    timed = 0;
+}
+
+frame_stats_t frame_stats() {
+    return stats;
+}
+
+fb_error swap_region_rect(rect_t r) {
+    return swap_region(r.x, r.y, r.w, r.h);
+}
+
+// Narrow the clip rectangle to its intersection with the given one. An empty
+// intersection is allowed and simply discards all writes until popped.
+fb_error push_clip(uint32_t x, uint32_t y, uint32_t width, uint32_t height) {
+    return push_clip_rect((rect_t){ x, y, width, height });
+}
+
+fb_error push_clip_rect(rect_t r) {
+    if (clip_depth >= CLIP_STACK_DEPTH) return CLIP_STACK_FULL;
+    rect_t cur = clip_rect();
+    clip_stack[clip_depth++] = cur;
+
+    rect_t out;
+    if (!rect_intersect(cur, r, &out))
+        out = (rect_t){ cur.x, cur.y, 0, 0 };
+    set_clip(out);
+    return OK;
+}
+
+fb_error pop_clip() {
+    if (clip_depth == clip_base) return CLIP_STACK_EMPTY;
+    set_clip(clip_stack[--clip_depth]);
+    return OK;
+}
+
+rect_t clip_rect() {
+    return (rect_t){ clip_x0, clip_y0, clip_x1 - clip_x0, clip_y1 - clip_y0 };
+}
+
+void clip_bounds(uint32_t *x, uint32_t *y, uint32_t *width, uint32_t *height) {
+    if (x)      *x      = clip_x0;
+    if (y)      *y      = clip_y0;
+    if (width)  *width  = clip_x1 - clip_x0;
+    if (height) *height = clip_y1 - clip_y0;
+}
+
+void enable_dirty_tracking(int enable) {
+    dirty_tracking = enable;
+    dirty_reset();
+}
+
+// Forget everything marked so far without presenting it.
+void clear_dirty() {
+    dirty_reset();
+}
+
+void mark_dirty(uint32_t x, uint32_t y, uint32_t width, uint32_t height) {
+    mark_dirty_rect((rect_t){ x, y, width, height });
+}
+
+void mark_dirty_rect(rect_t r) {
+    rect_t out;
+    if (rect_intersect(r, (rect_t){ 0, 0, back_w, back_h }, &out))
+        dirty_merge(out);
+}
+
+// Present everything drawn or marked since the last call, one rectangle at a
+// time, or the whole screen if more areas changed than are tracked.
+fb_error swap_dirty() {
+    fb_error err = OK;
+    if (dirty_overflow)
+        err = swap_region(0, 0, back_w, back_h);
+    else
+        for (uint32_t i = 0; i < dirty_count && err == OK; i++)
+            err = swap_region_rect(dirty[i]);
+    dirty_reset();
+    return err;
+}
+
+// An opaque colour: alpha is 0xFF, so the alpha-aware primitives draw it
+// the same as the plain ones.
 uint32_t color(uint8_t r, uint8_t g, uint8_t b) {
-    return ((uint32_t)r << 16) | ((uint32_t)g << 8) | b;
+    return rgba(r, g, b, 0xFF);
+}
+
+uint32_t rgba(uint8_t r, uint8_t g, uint8_t b, uint8_t a) {
+    return ((uint32_t)a << 24) | ((uint32_t)r << 16) | ((uint32_t)g << 8) | b;
+}
+
+uint8_t alpha_of(uint32_t c) { return (uint8_t)(c >> 24); }
+uint8_t red_of(uint32_t c)   { return (uint8_t)(c >> 16); }
+uint8_t green_of(uint32_t c) { return (uint8_t)(c >> 8); }
+uint8_t blue_of(uint32_t c)  { return (uint8_t)c; }
+
+// Stored pixels are opaque 0xFFRRGGBB: the result of a blend always has alpha
+// 0xFF, like color(), so a colour reads back the same however it was drawn.
+uint32_t blend_colors(uint32_t bg, uint32_t fg, uint8_t alpha) {
+    uint32_t inv = 255 - alpha;
+    uint32_t r = (((fg >> 16) & 0xFF) * alpha + ((bg >> 16) & 0xFF) * inv) / 255;
+    uint32_t g = (((fg >> 8)  & 0xFF) * alpha + ((bg >> 8)  & 0xFF) * inv) / 255;
+    uint32_t b = (( fg        & 0xFF) * alpha + ( bg        & 0xFF) * inv) / 255;
+    return 0xFF000000 | (r << 16) | (g << 8) | b;
 }
 
 fb_error draw_pixel(uint32_t x, uint32_t y, uint32_t c) {
-    if (x >= fb_width || y >= fb_height)
+    if (!in_clip(x, y))
         return FAILED_TO_DRAW;
     put_pixel(x, y, c);
     return OK;
 }
 
+// Composite a packed 0xAARRGGBB color over the pixel already at (x, y).
+fb_error blend_pixel(uint32_t x, uint32_t y, uint32_t argb) {
+    if (!in_clip(x, y))
+        return FAILED_TO_DRAW;
+    uint8_t a = alpha_of(argb);
+    if (a == 0) return OK;
+    put_pixel(x, y, a == 255 ? argb : blend_colors(get_pixel(x, y), argb, a));
+    return OK;
+}
+
+// blend_pixel() under the draw_pixel() family's name.
+fb_error draw_pixel_blended(uint32_t x, uint32_t y, uint32_t argb) {
+    return blend_pixel(x, y, argb);
+}
+
 uint32_t read_pixel(uint32_t x, uint32_t y) {
-    if (x >= fb_width || y >= fb_height)
+    if (x >= surf_w || y >= surf_h)
         return 0;
     return get_pixel(x, y);
 }
 
+// Like read_pixel(), but tells an off-surface read apart from a black pixel.
+fb_error try_read_pixel(uint32_t x, uint32_t y, uint32_t *out) {
+    if (!out) return NULL_POINTER;
+    if (x >= surf_w || y >= surf_h)
+        return OUT_OF_BOUNDS;
+    *out = get_pixel(x, y);
+    return OK;
+}
+
+// Copy a rectangle, clipped to the screen, into `out` row by row. The clipped
+// size is stored in *out_w / *out_h and `out` is packed with that width.
+fb_error read_region(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                     uint32_t *out, uint64_t out_len, uint32_t *out_w, uint32_t *out_h) {
+    if (!out) return NULL_POINTER;
+    uint32_t w = 0, h = 0;
+    if (x < surf_w && y < surf_h) {
+        w = (width  > surf_w - x) ? surf_w - x : width;
+        h = (height > surf_h - y) ? surf_h - y : height;
+    }
+    if (out_w) *out_w = w;
+    if (out_h) *out_h = h;
+    if ((uint64_t)w * h > out_len) return BUFFER_TOO_SMALL;
+
+    for (uint32_t row = 0; row < h; row++)
+        memcpy(out + (uint64_t)row * w, target + (y + row) * stride + x,
+               (int)(w * sizeof(uint32_t)));
+    return OK;
+}
+
+// Clip once up front and only walk the visible part. The end coordinates are
+// compared as remaining widths so x + width can never wrap around.
 fb_error draw_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height, uint32_t c) {
-    if (x >= fb_width || y >= fb_height) return FAILED_TO_DRAW;
-    uint32_t x_end = (x + width  > fb_width)  ? fb_width  : x + width;
-    uint32_t y_end = (y + height > fb_height) ? fb_height : y + height;
-    for (uint32_t row = y; row < y_end; row++)
-        for (uint32_t col = x; col < x_end; col++)
-            put_pixel(col, row, c);
+    if (x >= clip_x1 || y >= clip_y1) return FAILED_TO_DRAW;
+    uint32_t x_end = (width  > clip_x1 - x) ? clip_x1 : x + width;
+    uint32_t y_end = (height > clip_y1 - y) ? clip_y1 : y + height;
+    uint32_t x0 = x < clip_x0 ? clip_x0 : x;
+    uint32_t y0 = y < clip_y0 ? clip_y0 : y;
+    if (x0 >= x_end || y0 >= y_end) return OK;
+    if (global_alpha != 255) {
+        for (uint32_t row = y0; row < y_end; row++)
+            for (uint32_t col = x0; col < x_end; col++)
+                put_pixel(col, row, c);
+        return OK;
+    }
+    for (uint32_t row = y0; row < y_end; row++)
+        fill_row(target + row * stride + x0, x_end - x0, c);
+    if (dirty_tracking) dirty_add(x0, y0, x_end, y_end);
     return OK;
 }
 
@@ -71,6 +772,8 @@ fb_error draw_rect_outline(uint32_t x, uint32_t y, uint32_t width, uint32_t heig
 }
 
 fb_error draw_line(uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t c) {
+    if (antialias) return draw_line_aa(x1, y1, x2, y2, c);
+
     int dx  =  abs_i((int)x2 - (int)x1);
     int dy  = -abs_i((int)y2 - (int)y1);
     int sx  = x1 < x2 ? 1 : -1;
@@ -79,7 +782,7 @@ fb_error draw_line(uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t
 
     int cx = (int)x1, cy = (int)y1;
     while (1) {
-        if (cx >= 0 && cy >= 0 && (uint32_t)cx < fb_width && (uint32_t)cy < fb_height)
+        if (in_clip(cx, cy))
             put_pixel((uint32_t)cx, (uint32_t)cy, c);
         if (cx == (int)x2 && cy == (int)y2) break;
         int e2 = 2 * err;
@@ -89,16 +792,63 @@ fb_error draw_line(uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t
     return OK;
 }
 
+static inline void plot_coverage(int64_t x, int64_t y, uint32_t c, uint32_t coverage) {
+    if (!in_clip(x, y) || coverage == 0) return;
+    put_pixel((uint32_t)x, (uint32_t)y,
+              coverage >= 255 ? c : blend_colors(get_pixel((uint32_t)x, (uint32_t)y), c, coverage));
+}
+
+// Xiaolin Wu's line: walk the major axis and split each step between the two
+// nearest minor-axis pixels. The intercept is kept in 16.16 fixed point.
+fb_error draw_line_aa(uint32_t x1, uint32_t y1, uint32_t x2, uint32_t y2, uint32_t c) {
+    int64_t x0 = x1, y0 = y1, xe = x2, ye = y2, t;
+    int steep = abs_i((int)(ye - y0)) > abs_i((int)(xe - x0));
+    if (steep) { t = x0; x0 = y0; y0 = t; t = xe; xe = ye; ye = t; }
+    if (x0 > xe) { t = x0; x0 = xe; xe = t; t = y0; y0 = ye; ye = t; }
+
+    int64_t dx = xe - x0;
+    int64_t gradient = dx ? (ye - y0) * 65536 / dx : 0;
+    int64_t inter = y0 * 65536;
+
+    for (int64_t x = x0; x <= xe; x++) {
+        int64_t  iy   = inter >> 16;
+        uint32_t frac = (uint32_t)(inter & 0xFFFF) >> 8;
+        if (steep) {
+            plot_coverage(iy,     x, c, 255 - frac);
+            plot_coverage(iy + 1, x, c, frac);
+        } else {
+            plot_coverage(x, iy,     c, 255 - frac);
+            plot_coverage(x, iy + 1, c, frac);
+        }
+        inter += gradient;
+    }
+    return OK;
+}
+
 fb_error draw_circle(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t c) {
-    int r = (int)radius;
-    for (int y = -r; y <= r; y++)
-        for (int x = -r; x <= r; x++)
-            if (x*x + y*y <= r*r)
-                draw_pixel((uint32_t)((int)cx + x), (uint32_t)((int)cy + y), c);
+    if (antialias) return draw_circle_aa(cx, cy, radius, c);
+
+    // One span per row, covering every pixel with x*x + y*y <= r*r. The
+    // half-width only shrinks moving away from the centre row, so it is walked
+    // down instead of taking a square root per row.
+    int64_t r = radius, hw = r;
+    for (int64_t dy = 0; dy <= r; dy++) {
+        while (hw * hw + dy * dy > r * r) hw--;
+        int64_t x0 = (int64_t)cx - hw, x1 = (int64_t)cx + hw;
+        if (x0 < 0) x0 = 0;
+        if (x1 < 0) continue;
+        for (int side = 0; side < (dy ? 2 : 1); side++) {
+            int64_t row = side ? (int64_t)cy - dy : (int64_t)cy + dy;
+            if (row >= 0 && row <= UINT32_MAX)
+                draw_rect((uint32_t)x0, (uint32_t)row, (uint32_t)(x1 - x0 + 1), 1, c);
+        }
+    }
     return OK;
 }
 
 fb_error draw_circle_outline(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t c) {
+    if (antialias) return draw_circle_outline_aa(cx, cy, radius, c);
+
     int x = 0, y = (int)radius, d = 1 - (int)radius;
 
     #define PLOT8(px, py) do { \
@@ -128,23 +878,277 @@ fb_error draw_triangle(uint32_t x0, uint32_t y0,
     return OK;
 }
 
+static inline int64_t edge_fn(int64_t ax, int64_t ay, int64_t bx, int64_t by,
+                              int64_t px, int64_t py) {
+    return (bx - ax) * (py - ay) - (by - ay) * (px - ax);
+}
+
+// Top-left fill rule: pixels exactly on a top or left edge belong to this
+// triangle, those on other edges to its neighbour, so shared edges are drawn once.
+static inline int64_t edge_bias(int64_t ax, int64_t ay, int64_t bx, int64_t by) {
+    int64_t dx = bx - ax, dy = by - ay;
+    return (dy < 0 || (dy == 0 && dx > 0)) ? 0 : -1;
+}
+
+fb_error fill_triangle(uint32_t x0, uint32_t y0,
+                       uint32_t x1, uint32_t y1,
+                       uint32_t x2, uint32_t y2,
+                       uint32_t c) {
+    int64_t ax = x0, ay = y0, bx = x1, by = y1, qx = x2, qy = y2, t;
+    int64_t area = edge_fn(ax, ay, bx, by, qx, qy);
+    if (area == 0) return OK;
+    if (area < 0) { t = bx; bx = qx; qx = t; t = by; by = qy; qy = t; }
+
+    int64_t min_x = ax < bx ? (ax < qx ? ax : qx) : (bx < qx ? bx : qx);
+    int64_t min_y = ay < by ? (ay < qy ? ay : qy) : (by < qy ? by : qy);
+    int64_t max_x = ax > bx ? (ax > qx ? ax : qx) : (bx > qx ? bx : qx);
+    int64_t max_y = ay > by ? (ay > qy ? ay : qy) : (by > qy ? by : qy);
+    if (min_x < clip_x0) min_x = clip_x0;
+    if (min_y < clip_y0) min_y = clip_y0;
+    if (max_x >= clip_x1) max_x = (int64_t)clip_x1 - 1;
+    if (max_y >= clip_y1) max_y = (int64_t)clip_y1 - 1;
+
+    int64_t bias0 = edge_bias(bx, by, qx, qy);
+    int64_t bias1 = edge_bias(qx, qy, ax, ay);
+    int64_t bias2 = edge_bias(ax, ay, bx, by);
+
+    for (int64_t py = min_y; py <= max_y; py++) {
+        for (int64_t px = min_x; px <= max_x; px++) {
+            if (edge_fn(bx, by, qx, qy, px, py) + bias0 < 0) continue;
+            if (edge_fn(qx, qy, ax, ay, px, py) + bias1 < 0) continue;
+            if (edge_fn(ax, ay, bx, by, px, py) + bias2 < 0) continue;
+            put_pixel((uint32_t)px, (uint32_t)py, c);
+        }
+    }
+    return OK;
+}
+
+// Move the contents of a rectangle by (dx, dy) inside that same rectangle.
+// Whatever moves past its edges is dropped and the uncovered part is filled.
+// Rows are walked against the direction of motion so overlapping copies are
+// safe, and memmove takes care of overlap within a row.
+fb_error scroll_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                     int32_t dx, int32_t dy, uint32_t fill) {
+    if (x >= clip_x1 || y >= clip_y1) return FAILED_TO_DRAW;
+    int64_t x0 = x < clip_x0 ? clip_x0 : x;
+    int64_t y0 = y < clip_y0 ? clip_y0 : y;
+    int64_t x1 = (uint64_t)x + width  > clip_x1 ? clip_x1 : (int64_t)x + width;
+    int64_t y1 = (uint64_t)y + height > clip_y1 ? clip_y1 : (int64_t)y + height;
+    if (x0 >= x1 || y0 >= y1) return OK;
+
+    int64_t cx0 = x0 + dx > x0 ? x0 + dx : x0;
+    int64_t cx1 = x1 + dx < x1 ? x1 + dx : x1;
+
+    for (int64_t i = 0; i < y1 - y0; i++) {
+        int64_t row = dy > 0 ? y1 - 1 - i : y0 + i;
+        int64_t src = row - dy;
+        uint32_t *line = target + row * stride;
+
+        if (src >= y0 && src < y1 && cx0 < cx1) {
+            memmove(line + cx0, target + src * stride + (cx0 - dx),
+                    (int)((cx1 - cx0) * sizeof(uint32_t)));
+            for (int64_t col = x0; col < cx0; col++) line[col] = fill;
+            for (int64_t col = cx1; col < x1; col++) line[col] = fill;
+        } else {
+            for (int64_t col = x0; col < x1; col++) line[col] = fill;
+        }
+    }
+    if (dirty_tracking) dirty_add((uint32_t)x0, (uint32_t)y0, (uint32_t)x1, (uint32_t)y1);
+    return OK;
+}
+
 fb_error clear_screen(uint32_t c) {
-    return draw_rect(0, 0, fb_width, fb_height, c);
+    return draw_rect(0, 0, surf_w, surf_h, c);
+}
+
+// Clear the whole screen, ignoring the clip, in both the back buffer and the
+// visible framebuffer so no stale frame survives until the next swap.
+fb_error clear_all(uint32_t c) {
+    if (!fb_ptr) return NULL_POINTER;
+    if (!back_buf) {
+        for (uint32_t row = 0; row < fb_height; row++)
+            fill_row(fb_ptr + row * (fb_pitch / 4), fb_width, c);
+        return OK;
+    }
+    for (uint32_t row = 0; row < back_h; row++) {
+        fill_row(back_buf + row * back_w, back_w, c);
+        present_row(0, row, back_w);
+    }
+    prev_valid = 0;
+    return OK;
 }
 
 fb_error draw_bitmap(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
                      const uint32_t *bitmap) {
     if (!bitmap) return NULL_POINTER;
-    if (x >= fb_width || y >= fb_height) return FAILED_TO_DRAW;
-    uint32_t x_end = (x + width  > fb_width)  ? fb_width  : x + width;
-    uint32_t y_end = (y + height > fb_height) ? fb_height : y + height;
-    for (uint32_t row = y; row < y_end; row++)
-        for (uint32_t col = x; col < x_end; col++)
-            put_pixel(col, row, bitmap[(row - y) * width + (col - x)]);
+    if (x >= clip_x1 || y >= clip_y1) return FAILED_TO_DRAW;
+    uint32_t x_end = (width  > clip_x1 - x) ? clip_x1 : x + width;
+    uint32_t y_end = (height > clip_y1 - y) ? clip_y1 : y + height;
+    uint32_t x0 = x < clip_x0 ? clip_x0 : x;
+    uint32_t y0 = y < clip_y0 ? clip_y0 : y;
+    if (x0 >= x_end || y0 >= y_end) return OK;
+    if (global_alpha != 255) {
+        for (uint32_t row = y0; row < y_end; row++)
+            for (uint32_t col = x0; col < x_end; col++)
+                put_pixel(col, row, bitmap[(uint64_t)(row - y) * width + (col - x)]);
+        return OK;
+    }
+    for (uint32_t row = y0; row < y_end; row++)
+        memcpy(target + row * stride + x0, bitmap + (uint64_t)(row - y) * width + (x0 - x),
+               (int)((x_end - x0) * sizeof(uint32_t)));
+    if (dirty_tracking) dirty_add(x0, y0, x_end, y_end);
     return OK;
 }
 
-uint32_t display_width()  { return fb_width; }
-uint32_t display_height() { return fb_height; }
+// Blit a 0xAARRGGBB sprite, compositing each pixel by its own alpha.
+fb_error draw_sprite(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                     const uint32_t *pixels) {
+    if (!pixels) return NULL_POINTER;
+    if (x >= clip_x1 || y >= clip_y1) return FAILED_TO_DRAW;
+    uint32_t x_end = (width  > clip_x1 - x) ? clip_x1 : x + width;
+    uint32_t y_end = (height > clip_y1 - y) ? clip_y1 : y + height;
+    uint32_t x0 = x < clip_x0 ? clip_x0 : x;
+    uint32_t y0 = y < clip_y0 ? clip_y0 : y;
+    for (uint32_t row = y0; row < y_end; row++) {
+        for (uint32_t col = x0; col < x_end; col++) {
+            uint32_t src = pixels[(uint64_t)(row - y) * width + (col - x)];
+            uint8_t a = alpha_of(src);
+            if (a == 0) continue;
+            put_pixel(col, row, a == 255 ? src : blend_colors(get_pixel(col, row), src, a));
+        }
+    }
+    return OK;
+}
+
+// Straight copy for sprites known to be fully opaque.
+fb_error draw_sprite_opaque(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                            const uint32_t *pixels) {
+    return draw_bitmap(x, y, width, height, pixels);
+}
+
+canvas_t *canvas_new(uint32_t width, uint32_t height) {
+    if (width == 0 || height == 0) return 0;
+    canvas_t *canvas = malloc(sizeof(canvas_t));
+    if (!canvas) return 0;
+    canvas->buffer = malloc((uint64_t)width * height * sizeof(uint32_t));
+    if (!canvas->buffer) {
+        free(canvas);
+        return 0;
+    }
+    canvas->width  = width;
+    canvas->height = height;
+    canvas->stride = width;
+    for (uint32_t row = 0; row < height; row++)
+        fill_row(canvas->buffer + row * width, width, 0);
+    return canvas;
+}
+
+void canvas_free(canvas_t *canvas) {
+    if (!canvas) return;
+    if (screen.bound == canvas) bind_canvas(0);
+    free(canvas->buffer);
+    free(canvas);
+}
+
+// Redirect every drawing function to an off-screen canvas, with its own clip
+// stack and no dirty tracking. Binding NULL returns to the screen.
+fb_error bind_canvas(canvas_t *canvas) {
+    if (canvas && (!canvas->buffer || canvas->stride < canvas->width))
+        return INVALID_DISPLAY_PARAMS;
+
+    if (!screen.bound && canvas) {
+        screen.target         = target;
+        screen.stride         = stride;
+        screen.surf_w         = surf_w;
+        screen.surf_h         = surf_h;
+        screen.clip           = clip_rect();
+        screen.clip_depth     = clip_depth;
+        screen.clip_base      = clip_base;
+        screen.dirty_tracking = dirty_tracking;
+    }
+
+    if (!canvas) {
+        if (!screen.bound) return OK;
+        target         = screen.target;
+        stride         = screen.stride;
+        surf_w         = screen.surf_w;
+        surf_h         = screen.surf_h;
+        set_clip(screen.clip);
+        clip_depth     = screen.clip_depth;
+        clip_base      = screen.clip_base;
+        dirty_tracking = screen.dirty_tracking;
+        screen.bound   = 0;
+        return OK;
+    }
+
+    target         = canvas->buffer;
+    stride         = canvas->stride;
+    surf_w         = canvas->width;
+    surf_h         = canvas->height;
+    clip_x0        = clip_y0 = 0;
+    clip_x1        = canvas->width;
+    clip_y1        = canvas->height;
+    clip_depth     = clip_base = screen.clip_depth;
+    dirty_tracking = 0;
+    screen.bound   = canvas;
+    return OK;
+}
+
+uint32_t display_width()  { return back_w; }
+uint32_t display_height() { return back_h; }
 uint32_t display_pitch()  { return fb_pitch; }
-uint32_t *display_buffer() { return fb_ptr; }
\ No newline at end of file
+uint32_t *display_buffer() { return fb_ptr; }
+pixel_format display_format() { return fb_format; }
+
+// Direct access to the back buffer (0 until one exists), display_stride()
+// pixels per row: pixel (x, y) is at index y * stride + x. Writes made here
+// bypass dirty tracking, so mark_dirty() whatever was touched.
+uint32_t *display_back_buffer() { return back_buf; }
+uint32_t  display_stride()      { return back_w; }
+
+// Copy the whole back buffer out, e.g. to put the background back after a
+// transient overlay. *len receives the pixel count; free() the result.
+uint32_t *snapshot(uint64_t *len) {
+    if (len) *len = 0;
+    if (!back_buf) return 0;
+    uint64_t n = (uint64_t)back_w * back_h;
+    uint32_t *copy = malloc(n * sizeof(uint32_t));
+    if (!copy) return 0;
+    memcpy(copy, back_buf, (int)(n * sizeof(uint32_t)));
+    if (len) *len = n;
+    return copy;
+}
+
+// A snapshot of `len` pixels fits the current back buffer only if it has
+// exactly its size: a shorter one is too small, and a longer one was taken
+// from a bigger buffer whose rows would land in the wrong place.
+static fb_error check_snapshot_len(uint64_t len) {
+    uint64_t n = (uint64_t)back_w * back_h;
+    if (len < n) return BUFFER_TOO_SMALL;
+    if (len > n) return INVALID_DISPLAY_PARAMS;
+    return OK;
+}
+
+// Put a snapshot() back; the whole screen is marked dirty.
+fb_error restore(const uint32_t *pixels, uint64_t len) {
+    if (!back_buf || !pixels) return NULL_POINTER;
+    fb_error err = check_snapshot_len(len);
+    if (err != OK) return err;
+    uint64_t n = (uint64_t)back_w * back_h;
+    memcpy(back_buf, pixels, (int)(n * sizeof(uint32_t)));
+    if (dirty_tracking) dirty_add(0, 0, back_w, back_h);
+    return OK;
+}
+
+// Blend two snapshots into the back buffer, from at t = 0 to to at t = 255;
+// step t across frames for a fade between screens.
+fb_error crossfade(const uint32_t *from, const uint32_t *to, uint64_t len, uint8_t t) {
+    if (!back_buf || !from || !to) return NULL_POINTER;
+    fb_error err = check_snapshot_len(len);
+    if (err != OK) return err;
+    uint64_t n = (uint64_t)back_w * back_h;
+    for (uint64_t i = 0; i < n; i++)
+        back_buf[i] = mix(from[i], to[i], t);
+    if (dirty_tracking) dirty_add(0, 0, back_w, back_h);
+    return OK;
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+#include <display.h>
+
+typedef struct {
+    uint32_t x, y;
+} seed_t;
+
+typedef struct {
+    seed_t  *items;
+    uint64_t len, cap;
+} seed_stack_t;
+
+static int seed_push(seed_stack_t *s, uint32_t x, uint32_t y) {
+    if (s->len == s->cap) {
+        uint64_t cap = s->cap ? s->cap * 2 : 64;
+        seed_t *items = malloc(cap * sizeof(seed_t));
+        if (!items) return 0;
+        if (s->items) {
+            memcpy(items, s->items, (int)(s->len * sizeof(seed_t)));
+            free(s->items);
+        }
+        s->items = items;
+        s->cap   = cap;
+    }
+    s->items[s->len++] = (seed_t){ x, y };
+    return 1;
+}
+
+// Push a seed for every run of `match` pixels in row y between x0 and x1.
+static int seed_runs(seed_stack_t *s, uint32_t x0, uint32_t x1, uint32_t y, uint32_t match) {
+    int in_run = 0;
+    for (uint32_t x = x0; x < x1; x++) {
+        int hit = read_pixel(x, y) == match;
+        if (hit && !in_run && !seed_push(s, x, y)) return 0;
+        in_run = hit;
+    }
+    return 1;
+}
+
+// Scanline flood fill: replace the 4-connected region around (x, y) that has
+// the same color as (x, y) with `c`, staying inside the current clip. Global
+// alpha is ignored and the region is written with exactly `c`: a blended
+// pixel could still equal the colour being replaced and be filled forever.
+fb_error flood_fill(uint32_t x, uint32_t y, uint32_t c) {
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+    if (x < cx || y < cy || x - cx >= cw || y - cy >= ch) return FAILED_TO_DRAW;
+
+    uint32_t match = read_pixel(x, y);
+    if (match == c) return OK;
+
+    seed_stack_t s = { 0, 0, 0 };
+    if (!seed_push(&s, x, y)) return NULL_POINTER;
+
+    fb_error err = OK;
+    uint8_t alpha = set_global_alpha(255);
+    while (s.len) {
+        seed_t seed = s.items[--s.len];
+        if (read_pixel(seed.x, seed.y) != match) continue;
+
+        uint32_t x0 = seed.x, x1 = seed.x + 1;
+        while (x0 > cx && read_pixel(x0 - 1, seed.y) == match) x0--;
+        while (x1 < cx + cw && read_pixel(x1, seed.y) == match) x1++;
+        draw_rect(x0, seed.y, x1 - x0, 1, c);
+
+        if ((seed.y > cy && !seed_runs(&s, x0, x1, seed.y - 1, match)) ||
+            (seed.y + 1 < cy + ch && !seed_runs(&s, x0, x1, seed.y + 1, match))) {
+            err = NULL_POINTER;
+            break;
+        }
+    }
+    set_global_alpha(alpha);
+    free(s.items);
+    return err;
+}
@@ -0,0 +1,152 @@
+#include <stdint.h>
+#include <display.h>
+
+// Interpolate each channel from a to b by num / den. The products are taken
+// in 64 bits so the fraction stays exact however tall the gradient is. The
+// result is always opaque.
+static uint32_t lerp_color(uint32_t a, uint32_t b, int64_t num, int64_t den) {
+    if (den == 0) return a | 0xFF000000;
+    int64_t r = red_of(a)   + ((int64_t)red_of(b)   - red_of(a))   * num / den;
+    int64_t g = green_of(a) + ((int64_t)green_of(b) - green_of(a)) * num / den;
+    int64_t v = blue_of(a)  + ((int64_t)blue_of(b)  - blue_of(a))  * num / den;
+    return color((uint8_t)r, (uint8_t)g, (uint8_t)v);
+}
+
+// sRGB channel value -> linear light, scaled to 0..4095.
+static const uint16_t srgb_to_linear[256] = {
+       0,    1,    2,    4,    5,    6,    7,    9,   10,   11,   12,   14,
+      15,   16,   18,   20,   21,   23,   25,   27,   29,   31,   33,   35,
+      37,   40,   42,   45,   48,   50,   53,   56,   59,   62,   66,   69,
+      72,   76,   79,   83,   87,   91,   95,   99,  103,  107,  112,  116,
+     121,  126,  131,  136,  141,  146,  151,  156,  162,  168,  173,  179,
+     185,  191,  197,  204,  210,  216,  223,  230,  237,  244,  251,  258,
+     265,  273,  280,  288,  296,  304,  312,  320,  329,  337,  346,  354,
+     363,  372,  381,  390,  400,  409,  419,  428,  438,  448,  458,  469,
+     479,  490,  500,  511,  522,  533,  544,  555,  567,  578,  590,  602,
+     614,  626,  639,  651,  664,  676,  689,  702,  715,  728,  742,  755,
+     769,  783,  797,  811,  825,  840,  854,  869,  884,  899,  914,  929,
+     945,  960,  976,  992, 1008, 1024, 1041, 1057, 1074, 1091, 1108, 1125,
+    1142, 1159, 1177, 1195, 1213, 1231, 1249, 1267, 1286, 1304, 1323, 1342,
+    1361, 1381, 1400, 1420, 1440, 1459, 1480, 1500, 1520, 1541, 1562, 1582,
+    1603, 1625, 1646, 1668, 1689, 1711, 1733, 1755, 1778, 1800, 1823, 1846,
+    1869, 1892, 1916, 1939, 1963, 1987, 2011, 2035, 2059, 2084, 2109, 2133,
+    2159, 2184, 2209, 2235, 2260, 2286, 2312, 2339, 2365, 2392, 2419, 2446,
+    2473, 2500, 2527, 2555, 2583, 2611, 2639, 2668, 2696, 2725, 2754, 2783,
+    2812, 2841, 2871, 2901, 2931, 2961, 2991, 3022, 3052, 3083, 3114, 3146,
+    3177, 3209, 3240, 3272, 3304, 3337, 3369, 3402, 3435, 3468, 3501, 3535,
+    3568, 3602, 3636, 3670, 3705, 3739, 3774, 3809, 3844, 3879, 3915, 3950,
+    3986, 4022, 4059, 4095,
+};
+
+// Inverse of srgb_to_linear: the sRGB value whose linear level is closest.
+static uint8_t linear_to_srgb(uint32_t lin) {
+    uint32_t lo = 0, hi = 255;
+    while (lo < hi) {
+        uint32_t mid = (lo + hi) / 2;
+        if (srgb_to_linear[mid] < lin) lo = mid + 1;
+        else hi = mid;
+    }
+    if (lo > 0 && lin - srgb_to_linear[lo - 1] < srgb_to_linear[lo] - lin) lo--;
+    return (uint8_t)lo;
+}
+
+static uint8_t lerp_channel_srgb(uint8_t a, uint8_t b, int64_t num, int64_t den) {
+    int64_t la = srgb_to_linear[a], lb = srgb_to_linear[b];
+    return linear_to_srgb((uint32_t)(la + (lb - la) * num / den));
+}
+
+// Interpolate in linear light, so the midpoint of two saturated colors keeps
+// its brightness instead of dipping into a muddy dark band.
+static uint32_t lerp_color_srgb(uint32_t a, uint32_t b, int64_t num, int64_t den) {
+    if (den == 0) return a | 0xFF000000;
+    return color(lerp_channel_srgb(red_of(a),   red_of(b),   num, den),
+                 lerp_channel_srgb(green_of(a), green_of(b), num, den),
+                 lerp_channel_srgb(blue_of(a),  blue_of(b),  num, den));
+}
+
+typedef uint32_t (*lerp_fn)(uint32_t a, uint32_t b, int64_t num, int64_t den);
+
+// Color of sample `i` of `n` evenly spaced along the stops, so the first and
+// last samples land exactly on the end stops. Sample i sits at position
+// i * 255 / (n - 1); that fraction is kept exact rather than rounded to a
+// stop position, so a two-stop gradient is a plain lerp over every row.
+static uint32_t stops_color(const gradient_stop_t *stops, uint32_t count,
+                            uint32_t i, uint32_t n, lerp_fn lerp) {
+    uint64_t scale = n > 1 ? n - 1 : 1;
+    uint64_t pos   = n > 1 ? (uint64_t)i * 255 : 0;
+    uint32_t seg   = 0;
+    while (seg + 2 < count && pos > stops[seg + 1].pos * scale) seg++;
+
+    const gradient_stop_t *a = &stops[seg], *b = &stops[seg + 1];
+    if (b->pos == a->pos) return b->color | 0xFF000000;
+    return lerp(a->color, b->color, pos - a->pos * scale, (b->pos - a->pos) * scale);
+}
+
+// Narrow [start, start + len) to the clip span [lo, lo + n), as offsets from
+// start. Returns 0 when nothing is visible.
+static int visible_span(uint32_t start, uint32_t len, uint32_t lo, uint32_t n,
+                        uint32_t *first, uint32_t *end) {
+    uint64_t s = start, e = (uint64_t)start + len;
+    uint64_t a = s > lo ? s : lo, b = e < (uint64_t)lo + n ? e : (uint64_t)lo + n;
+    if (a >= b) return 0;
+    *first = (uint32_t)(a - s);
+    *end   = (uint32_t)(b - s);
+    return 1;
+}
+
+// One solid row per step, and only for the rows inside the clip. The first
+// row takes the first stop's color and the last row the last stop's.
+static fb_error gradient_rows(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                              const gradient_stop_t *stops, uint32_t count, lerp_fn lerp) {
+    uint32_t cy, ch, first, end;
+    clip_bounds(0, &cy, 0, &ch);
+    if (!visible_span(y, height, cy, ch, &first, &end)) return OK;
+    for (uint32_t row = first; row < end; row++)
+        draw_rect(x, y + row, width, 1, stops_color(stops, count, row, height, lerp));
+    return OK;
+}
+
+static fb_error gradient_cols(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                              const gradient_stop_t *stops, uint32_t count, lerp_fn lerp) {
+    uint32_t cx, cw, first, end;
+    clip_bounds(&cx, 0, &cw, 0);
+    if (!visible_span(x, width, cx, cw, &first, &end)) return OK;
+    for (uint32_t col = first; col < end; col++)
+        draw_rect(x + col, y, 1, height, stops_color(stops, count, col, width, lerp));
+    return OK;
+}
+
+fb_error draw_gradient_vertical(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                                uint32_t top, uint32_t bottom) {
+    const gradient_stop_t stops[] = { { 0, top }, { 255, bottom } };
+    return gradient_rows(x, y, width, height, stops, 2, lerp_color);
+}
+
+fb_error draw_gradient_horizontal(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                                  uint32_t left, uint32_t right) {
+    const gradient_stop_t stops[] = { { 0, left }, { 255, right } };
+    return gradient_cols(x, y, width, height, stops, 2, lerp_color);
+}
+
+fb_error draw_gradient_vertical_srgb(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                                     uint32_t top, uint32_t bottom) {
+    const gradient_stop_t stops[] = { { 0, top }, { 255, bottom } };
+    return gradient_rows(x, y, width, height, stops, 2, lerp_color_srgb);
+}
+
+fb_error draw_gradient_horizontal_srgb(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                                       uint32_t left, uint32_t right) {
+    const gradient_stop_t stops[] = { { 0, left }, { 255, right } };
+    return gradient_cols(x, y, width, height, stops, 2, lerp_color_srgb);
+}
+
+// Stops must be sorted by position, starting at 0 and ending at 255.
+fb_error draw_gradient_stops_vertical(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                                      const gradient_stop_t *stops, uint32_t count) {
+    if (!stops) return NULL_POINTER;
+    if (count < 2 || stops[0].pos != 0 || stops[count - 1].pos != 255)
+        return INVALID_DISPLAY_PARAMS;
+    for (uint32_t i = 1; i < count; i++)
+        if (stops[i].pos < stops[i - 1].pos) return INVALID_DISPLAY_PARAMS;
+    return gradient_rows(x, y, width, height, stops, count, lerp_color);
+}
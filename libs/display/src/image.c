@@ -0,0 +1,250 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <display.h>
+
+static inline uint16_t read_u16(const uint8_t *p) {
+    return (uint16_t)(p[0] | (p[1] << 8));
+}
+
+static inline uint32_t read_u32(const uint8_t *p) {
+    return (uint32_t)p[0] | ((uint32_t)p[1] << 8) | ((uint32_t)p[2] << 16) | ((uint32_t)p[3] << 24);
+}
+
+// Run-length encoded image: a sequence of 6-byte runs, each a little-endian
+// u16 pixel count followed by a little-endian u32 0xAARRGGBB color, covering
+// the image row by row. The runs must add up to exactly width * height.
+#define RLE_RUN_SIZE 6
+
+fb_error draw_rle(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                  const uint8_t *data, uint64_t len) {
+    if (!data) return NULL_POINTER;
+    if (len % RLE_RUN_SIZE) return MALFORMED_DATA;
+
+    uint64_t total = 0;
+    for (uint64_t off = 0; off < len; off += RLE_RUN_SIZE)
+        total += read_u16(data + off);
+    if (total != (uint64_t)width * height) return MALFORMED_DATA;
+
+    uint64_t i = 0;
+    for (uint64_t off = 0; off < len; off += RLE_RUN_SIZE) {
+        uint32_t count = read_u16(data + off);
+        uint32_t c     = read_u32(data + off + 2);
+        for (; count; count--, i++) {
+            uint64_t px = x + i % width, py = y + i / width;
+            if (px <= UINT32_MAX && py <= UINT32_MAX)
+                blend_pixel((uint32_t)px, (uint32_t)py, c);
+        }
+    }
+    return OK;
+}
+
+#define BMP_FILE_HEADER_SIZE 14
+#define BMP_INFO_HEADER_SIZE 40
+
+static inline int32_t read_i32(const uint8_t *p) {
+    return (int32_t)read_u32(p);
+}
+
+// Decode an uncompressed 24- or 32-bit BMP with a BITMAPINFOHEADER (or a
+// later, larger header) into 0xFFRRGGBB pixels. Rows are stored bottom-up
+// unless the height is negative. The pixels are allocated; see image_free().
+image_error bmp_parse(image_t *img, const uint8_t *data, uint64_t len) {
+    if (!img || !data) return IMAGE_TRUNCATED;
+    if (len < BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE) return IMAGE_TRUNCATED;
+    if (data[0] != 'B' || data[1] != 'M') return IMAGE_BAD_MAGIC;
+
+    uint32_t offset      = read_u32(data + 10);
+    uint32_t header_size = read_u32(data + 14);
+    int32_t  width       = read_i32(data + 18);
+    int32_t  height      = read_i32(data + 22);
+    uint16_t planes      = read_u16(data + 26);
+    uint16_t bpp         = read_u16(data + 28);
+    uint32_t compression = read_u32(data + 30);
+
+    if (header_size < BMP_INFO_HEADER_SIZE || planes != 1) return IMAGE_BAD_HEADER;
+    if (width <= 0 || height == 0 || height == INT32_MIN) return IMAGE_BAD_HEADER;
+    if ((bpp != 24 && bpp != 32) || compression != 0) return IMAGE_UNSUPPORTED;
+
+    int      top_down = height < 0;
+    uint32_t w = (uint32_t)width, h = (uint32_t)(top_down ? -height : height);
+    uint32_t bytes = bpp / 8;
+    uint64_t row_size = ((uint64_t)w * bytes + 3) & ~(uint64_t)3;
+    if (offset < BMP_FILE_HEADER_SIZE + header_size || offset > len ||
+        row_size * h > len - offset)
+        return IMAGE_TRUNCATED;
+
+    uint32_t *pixels = malloc((uint64_t)w * h * sizeof(uint32_t));
+    if (!pixels) return IMAGE_NO_MEMORY;
+    for (uint32_t row = 0; row < h; row++) {
+        const uint8_t *src = data + offset + row_size * (top_down ? row : h - 1 - row);
+        uint32_t *dst = pixels + (uint64_t)row * w;
+        for (uint32_t col = 0; col < w; col++, src += bytes)
+            dst[col] = rgba(src[2], src[1], src[0], 0xFF);
+    }
+
+    img->width  = w;
+    img->height = h;
+    img->pixels = pixels;
+    return IMAGE_OK;
+}
+
+void image_free(image_t *img) {
+    if (!img) return;
+    free(img->pixels);
+    img->pixels = 0;
+    img->width  = 0;
+    img->height = 0;
+}
+
+// Blit a decoded image, compositing by its per-pixel alpha.
+fb_error draw_image(uint32_t x, uint32_t y, const image_t *img) {
+    if (!img || !img->pixels) return NULL_POINTER;
+    return draw_sprite(x, y, img->width, img->height, img->pixels);
+}
+
+#define QOI_HEADER_SIZE 14
+#define QOI_END_SIZE    8
+#define QOI_OP_INDEX    0x00
+#define QOI_OP_DIFF     0x40
+#define QOI_OP_LUMA     0x80
+#define QOI_OP_RUN      0xC0
+#define QOI_OP_RGB      0xFE
+#define QOI_OP_RGBA     0xFF
+#define QOI_MASK_2      0xC0
+#define QOI_MAX_PIXELS  400000000u
+
+static const uint8_t qoi_end[QOI_END_SIZE] = { 0, 0, 0, 0, 0, 0, 0, 1 };
+
+static inline uint32_t read_u32_be(const uint8_t *p) {
+    return ((uint32_t)p[0] << 24) | ((uint32_t)p[1] << 16) | ((uint32_t)p[2] << 8) | p[3];
+}
+
+// Decode a QOI image into 0xAARRGGBB pixels, following the format spec: a
+// 14-byte header, a stream of index/diff/luma/run/RGB/RGBA chunks, and an
+// 8-byte end marker. The pixels are allocated; see image_free().
+image_error qoi_decode(image_t *img, const uint8_t *data, uint64_t len) {
+    if (!img || !data) return IMAGE_TRUNCATED;
+    if (len < QOI_HEADER_SIZE + QOI_END_SIZE) return IMAGE_TRUNCATED;
+    if (data[0] != 'q' || data[1] != 'o' || data[2] != 'i' || data[3] != 'f')
+        return IMAGE_BAD_MAGIC;
+    for (int i = 0; i < QOI_END_SIZE; i++)
+        if (data[len - QOI_END_SIZE + i] != qoi_end[i]) return IMAGE_TRUNCATED;
+
+    uint32_t w = read_u32_be(data + 4), h = read_u32_be(data + 8);
+    uint8_t channels = data[12], colorspace = data[13];
+    if (w == 0 || h == 0 || (channels != 3 && channels != 4) || colorspace > 1)
+        return IMAGE_BAD_HEADER;
+    if ((uint64_t)w * h > QOI_MAX_PIXELS) return IMAGE_UNSUPPORTED;
+
+    uint64_t count = (uint64_t)w * h;
+    uint32_t *pixels = malloc(count * sizeof(uint32_t));
+    if (!pixels) return IMAGE_NO_MEMORY;
+
+    uint32_t index[64] = { 0 };
+    uint8_t  r = 0, g = 0, b = 0, a = 255;
+    uint64_t pos = QOI_HEADER_SIZE, end = len - QOI_END_SIZE;
+    uint32_t run = 0;
+
+    for (uint64_t i = 0; i < count; i++) {
+        if (run) {
+            run--;
+        } else {
+            if (pos >= end) {
+                free(pixels);
+                return IMAGE_TRUNCATED;
+            }
+            uint8_t op = data[pos++];
+            if (op == QOI_OP_RGB || op == QOI_OP_RGBA) {
+                uint64_t need = op == QOI_OP_RGB ? 3 : 4;
+                if (end - pos < need) {
+                    free(pixels);
+                    return IMAGE_TRUNCATED;
+                }
+                r = data[pos++];
+                g = data[pos++];
+                b = data[pos++];
+                if (op == QOI_OP_RGBA) a = data[pos++];
+            } else if ((op & QOI_MASK_2) == QOI_OP_INDEX) {
+                uint32_t p = index[op];
+                a = alpha_of(p); r = red_of(p); g = green_of(p); b = blue_of(p);
+            } else if ((op & QOI_MASK_2) == QOI_OP_DIFF) {
+                r += ((op >> 4) & 3) - 2;
+                g += ((op >> 2) & 3) - 2;
+                b += (op & 3) - 2;
+            } else if ((op & QOI_MASK_2) == QOI_OP_LUMA) {
+                if (pos >= end) {
+                    free(pixels);
+                    return IMAGE_TRUNCATED;
+                }
+                uint8_t next = data[pos++];
+                int dg = (op & 0x3F) - 32;
+                r += dg - 8 + ((next >> 4) & 0x0F);
+                g += dg;
+                b += dg - 8 + (next & 0x0F);
+            } else {
+                run = op & 0x3F;
+            }
+            index[(r * 3 + g * 5 + b * 7 + a * 11) % 64] = rgba(r, g, b, a);
+        }
+        pixels[i] = rgba(r, g, b, a);
+    }
+
+    img->width  = w;
+    img->height = h;
+    img->pixels = pixels;
+    return IMAGE_OK;
+}
+
+// Decode a QOI image, blit it, and free it again. A decoded image that
+// draw_image() refuses, e.g. one placed outside the clip, is reported as
+// IMAGE_DRAW_FAILED.
+image_error draw_qoi(uint32_t x, uint32_t y, const uint8_t *data, uint64_t len) {
+    image_t img;
+    image_error err = qoi_decode(&img, data, len);
+    if (err != IMAGE_OK) return err;
+    fb_error drawn = draw_image(x, y, &img);
+    image_free(&img);
+    return drawn == OK ? IMAGE_OK : IMAGE_DRAW_FAILED;
+}
+
+// Shrink an image into a newly allocated dst_w x dst_h one, each destination
+// pixel the per-channel average of the block of source pixels it covers.
+// Non-integer ratios give blocks of slightly varying size.
+image_error image_downscale(image_t *dst, const image_t *src, uint32_t dst_w, uint32_t dst_h) {
+    if (!dst || !src || !src->pixels) return IMAGE_TRUNCATED;
+    if (dst_w == 0 || dst_h == 0 || src->width == 0 || src->height == 0) return IMAGE_BAD_HEADER;
+
+    uint32_t *pixels = malloc((uint64_t)dst_w * dst_h * sizeof(uint32_t));
+    if (!pixels) return IMAGE_NO_MEMORY;
+
+    for (uint32_t dy = 0; dy < dst_h; dy++) {
+        uint32_t sy0 = (uint32_t)((uint64_t)dy * src->height / dst_h);
+        uint32_t sy1 = (uint32_t)((uint64_t)(dy + 1) * src->height / dst_h);
+        if (sy1 <= sy0) sy1 = sy0 + 1;
+        for (uint32_t dx = 0; dx < dst_w; dx++) {
+            uint32_t sx0 = (uint32_t)((uint64_t)dx * src->width / dst_w);
+            uint32_t sx1 = (uint32_t)((uint64_t)(dx + 1) * src->width / dst_w);
+            if (sx1 <= sx0) sx1 = sx0 + 1;
+
+            uint64_t a = 0, r = 0, g = 0, b = 0;
+            for (uint32_t sy = sy0; sy < sy1; sy++) {
+                for (uint32_t sx = sx0; sx < sx1; sx++) {
+                    uint32_t p = src->pixels[(uint64_t)sy * src->width + sx];
+                    a += alpha_of(p);
+                    r += red_of(p);
+                    g += green_of(p);
+                    b += blue_of(p);
+                }
+            }
+            uint64_t n = (uint64_t)(sx1 - sx0) * (sy1 - sy0);
+            pixels[(uint64_t)dy * dst_w + dx] =
+                rgba((uint8_t)((r + n / 2) / n), (uint8_t)((g + n / 2) / n),
+                     (uint8_t)((b + n / 2) / n), (uint8_t)((a + n / 2) / n));
+        }
+    }
+
+    dst->width  = dst_w;
+    dst->height = dst_h;
+    dst->pixels = pixels;
+    return IMAGE_OK;
+}
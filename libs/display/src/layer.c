@@ -0,0 +1,52 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <display.h>
+
+layer_t *layer_new(uint32_t width, uint32_t height) {
+    layer_t *layer = malloc(sizeof(layer_t));
+    if (!layer) return 0;
+    layer->canvas = canvas_new(width, height);
+    if (!layer->canvas) {
+        free(layer);
+        return 0;
+    }
+    layer->x       = 0;
+    layer->y       = 0;
+    layer->opacity = 255;
+    return layer;
+}
+
+void layer_free(layer_t *layer) {
+    if (!layer) return;
+    canvas_free(layer->canvas);
+    free(layer);
+}
+
+// Alpha-blend a layer over the current target at its position, scaling every
+// pixel's alpha by the layer's opacity. Only the part inside the clip is drawn.
+fb_error composite_layer(const layer_t *layer) {
+    if (!layer || !layer->canvas) return NULL_POINTER;
+    if (layer->opacity == 0) return OK;
+
+    const canvas_t *canvas = layer->canvas;
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+
+    int64_t x0 = layer->x > (int64_t)cx ? layer->x : (int64_t)cx;
+    int64_t y0 = layer->y > (int64_t)cy ? layer->y : (int64_t)cy;
+    int64_t x1 = (int64_t)layer->x + canvas->width;
+    int64_t y1 = (int64_t)layer->y + canvas->height;
+    if (x1 > (int64_t)cx + cw) x1 = (int64_t)cx + cw;
+    if (y1 > (int64_t)cy + ch) y1 = (int64_t)cy + ch;
+    if (x0 >= x1 || y0 >= y1) return OK;
+
+    for (int64_t row = y0; row < y1; row++) {
+        const uint32_t *src = canvas->buffer + (row - layer->y) * canvas->stride;
+        for (int64_t col = x0; col < x1; col++) {
+            uint32_t p = src[col - layer->x];
+            uint32_t a = alpha_of(p) * layer->opacity / 255;
+            blend_pixel((uint32_t)col, (uint32_t)row, (a << 24) | (p & 0xFFFFFF));
+        }
+    }
+    return OK;
+}
@@ -0,0 +1,54 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <display.h>
+
+// Background under the cursor as it was before the last draw_cursor().
+static uint32_t *saved     = 0;
+static uint64_t  saved_cap = 0;
+static uint32_t  saved_x, saved_y, saved_w, saved_h;
+static int       saved_valid = 0;
+
+// Blend a cursor sprite at (x, y), first saving the background it covers so
+// erase_cursor() can put it back. A cursor that is still drawn is erased first.
+fb_error draw_cursor(int32_t x, int32_t y, uint32_t width, uint32_t height, const uint32_t *pixels) {
+    if (!pixels) return NULL_POINTER;
+    erase_cursor();
+
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+    int64_t x0 = x > (int64_t)cx ? x : (int64_t)cx;
+    int64_t y0 = y > (int64_t)cy ? y : (int64_t)cy;
+    int64_t x1 = (int64_t)x + width, y1 = (int64_t)y + height;
+    if (x1 > (int64_t)cx + cw) x1 = (int64_t)cx + cw;
+    if (y1 > (int64_t)cy + ch) y1 = (int64_t)cy + ch;
+    if (x0 >= x1 || y0 >= y1) return OK;
+
+    uint64_t need = (uint64_t)(x1 - x0) * (uint64_t)(y1 - y0);
+    if (need > saved_cap) {
+        free(saved);
+        saved = malloc(need * sizeof(uint32_t));
+        saved_cap = saved ? need : 0;
+        if (!saved) return NULL_POINTER;
+    }
+
+    fb_error err = read_region((uint32_t)x0, (uint32_t)y0, (uint32_t)(x1 - x0), (uint32_t)(y1 - y0),
+                               saved, saved_cap, &saved_w, &saved_h);
+    if (err != OK) return err;
+    saved_x = (uint32_t)x0;
+    saved_y = (uint32_t)y0;
+    saved_valid = 1;
+
+    for (int64_t row = y0; row < y1; row++) {
+        const uint32_t *src = pixels + (row - y) * width;
+        for (int64_t col = x0; col < x1; col++)
+            blend_pixel((uint32_t)col, (uint32_t)row, src[col - x]);
+    }
+    return OK;
+}
+
+// Restore the background saved by the last draw_cursor().
+fb_error erase_cursor() {
+    if (!saved_valid) return OK;
+    saved_valid = 0;
+    return draw_bitmap(saved_x, saved_y, saved_w, saved_h, saved);
+}
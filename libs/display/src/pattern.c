@@ -0,0 +1,49 @@
+#include <stdint.h>
+#include <display.h>
+
+// Alternate color_a and color_b in cell x cell squares, color_a at the top-left.
+// Only the cells that overlap the clip are visited.
+fb_error fill_checker(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                      uint32_t cell, uint32_t color_a, uint32_t color_b) {
+    if (cell == 0 || width == 0 || height == 0) return OK;
+
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+    uint64_t x1 = (uint64_t)x + width, y1 = (uint64_t)y + height;
+    if (x1 > (uint64_t)cx + cw) x1 = (uint64_t)cx + cw;
+    if (y1 > (uint64_t)cy + ch) y1 = (uint64_t)cy + ch;
+    uint64_t col0 = cx > x ? (cx - x) / cell : 0;
+    uint64_t row0 = cy > y ? (cy - y) / cell : 0;
+
+    for (uint64_t row = row0; y + row * cell < y1; row++) {
+        uint64_t top = y + row * cell;
+        uint64_t h   = top + cell < y1 ? cell : y1 - top;
+        for (uint64_t col = col0; x + col * cell < x1; col++) {
+            uint64_t left = x + col * cell;
+            uint64_t w    = left + cell < x1 ? cell : x1 - left;
+            draw_rect((uint32_t)left, (uint32_t)top, (uint32_t)w, (uint32_t)h,
+                      (row + col) & 1 ? color_b : color_a);
+        }
+    }
+    return OK;
+}
+
+// A single-pixel dot every `spacing` pixels in both directions, starting at (x, y).
+fb_error fill_dots(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                   uint32_t spacing, uint32_t c) {
+    if (spacing == 0 || width == 0 || height == 0) return OK;
+
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+    uint64_t x1 = (uint64_t)x + width, y1 = (uint64_t)y + height;
+    if (x1 > (uint64_t)cx + cw) x1 = (uint64_t)cx + cw;
+    if (y1 > (uint64_t)cy + ch) y1 = (uint64_t)cy + ch;
+    uint64_t px0 = x, py0 = y;
+    if (cx > x) px0 += ((uint64_t)cx - x + spacing - 1) / spacing * spacing;
+    if (cy > y) py0 += ((uint64_t)cy - y + spacing - 1) / spacing * spacing;
+
+    for (uint64_t py = py0; py < y1; py += spacing)
+        for (uint64_t px = px0; px < x1; px += spacing)
+            draw_pixel((uint32_t)px, (uint32_t)py, c);
+    return OK;
+}
@@ -0,0 +1,590 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <display.h>
+
+static uint32_t isqrt(uint64_t n) {
+    uint64_t x = n, y = (x + 1) / 2;
+    while (y < x) {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    return (uint32_t)x;
+}
+
+// Horizontal extent [*x0, *x1] of row py within a rounded rectangle, whose
+// corners are quarter circles of radius r. Returns 0 if py is outside it.
+static int rounded_span(int64_t x, int64_t y, int64_t width, int64_t height, int64_t r,
+                        int64_t py, int64_t *x0, int64_t *x1) {
+    if (width <= 0 || height <= 0 || py < y || py >= y + height) return 0;
+    int64_t cx_l = x + r, cx_r = x + width  - 1 - r;
+    int64_t cy_t = y + r, cy_b = y + height - 1 - r;
+    int64_t dy   = py < cy_t ? cy_t - py : (py > cy_b ? py - cy_b : 0);
+    int64_t span = isqrt((uint64_t)(r * r - dy * dy));
+    *x0 = cx_l - span;
+    *x1 = cx_r + span;
+    return 1;
+}
+
+static inline void draw_span(int64_t x0, int64_t x1, int64_t py, uint32_t c) {
+    if (x1 < x0) return;
+    draw_rect((uint32_t)x0, (uint32_t)py, (uint32_t)(x1 - x0 + 1), 1, c);
+}
+
+// Each row is one span between the left and right corner circles, so nothing
+// is drawn twice and the straight edges have no seams.
+fb_error fill_rounded_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                           uint32_t radius, uint32_t c) {
+    if (width == 0 || height == 0) return OK;
+    uint32_t max_r = (width < height ? width : height) / 2;
+    if (radius > max_r) radius = max_r;
+
+    int64_t x0, x1;
+    for (int64_t py = y; py < (int64_t)y + height; py++)
+        if (rounded_span(x, y, width, height, radius, py, &x0, &x1))
+            draw_span(x0, x1, py, c);
+    return OK;
+}
+
+// Stroke the band between the outer rounded rectangle and one inset by
+// `thickness` on every side, whose corner radius shrinks by the same amount.
+fb_error draw_rounded_rect_outline(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                                   uint32_t radius, uint32_t thickness, uint32_t c) {
+    if (width == 0 || height == 0 || thickness == 0) return OK;
+    uint32_t max_r = (width < height ? width : height) / 2;
+    if (radius    > max_r) radius    = max_r;
+    if (thickness > max_r) thickness = max_r;
+
+    int64_t t  = thickness;
+    int64_t ir = radius > thickness ? radius - thickness : 0;
+    int64_t ox0, ox1, ix0, ix1;
+    for (int64_t py = y; py < (int64_t)y + height; py++) {
+        if (!rounded_span(x, y, width, height, radius, py, &ox0, &ox1)) continue;
+        if (!rounded_span((int64_t)x + t, (int64_t)y + t, (int64_t)width - 2 * t,
+                          (int64_t)height - 2 * t, ir, py, &ix0, &ix1)) {
+            draw_span(ox0, ox1, py, c);
+            continue;
+        }
+        draw_span(ox0, ix0 - 1, py, c);
+        draw_span(ix1 + 1, ox1, py, c);
+    }
+    return OK;
+}
+
+// Raised panel: filled with `fill`, with `bevel` pixels of `light` along the
+// top and left edges and of `dark` along the bottom and right. The top and
+// bottom bands span the full width; the side bands fill in between them.
+fb_error draw_bevel_rect(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                         uint32_t fill, uint32_t light, uint32_t dark, uint32_t bevel) {
+    if (width == 0 || height == 0) return OK;
+    uint32_t max_b = (width < height ? width : height) / 2;
+    if (bevel > max_b) bevel = max_b;
+
+    draw_rect(x, y, width, height, fill);
+    if (bevel == 0) return OK;
+    draw_rect(x, y,                   width, bevel, light);
+    draw_rect(x, y + height - bevel,  width, bevel, dark);
+    draw_rect(x,                 y + bevel, bevel, height - 2 * bevel, light);
+    draw_rect(x + width - bevel, y + bevel, bevel, height - 2 * bevel, dark);
+    return OK;
+}
+
+static inline void plot(int64_t x, int64_t y, uint32_t c) {
+    if (x >= 0 && y >= 0 && x <= UINT32_MAX && y <= UINT32_MAX)
+        draw_pixel((uint32_t)x, (uint32_t)y, c);
+}
+
+// Draws c over the existing pixel at `coverage`/255 opacity. Like
+// draw_line_aa(), c is treated as opaque whatever its alpha byte says.
+static inline void plot_cover(int64_t x, int64_t y, uint32_t c, uint32_t coverage) {
+    if (x < 0 || y < 0 || x > UINT32_MAX || y > UINT32_MAX || coverage == 0) return;
+    blend_pixel((uint32_t)x, (uint32_t)y, (coverage << 24) | (c & 0x00FFFFFF));
+}
+
+// Coverage of a pixel is how far its centre lies inside the edge, in 1/256ths
+// of a pixel; only the one-pixel ring around the radius needs a square root.
+// The whole disc is further scaled by `opacity`/255.
+static void aa_disc(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t c, uint32_t opacity) {
+    int64_t r = radius, inner = r > 0 ? (r - 1) * (r - 1) : -1, outer = (r + 1) * (r + 1);
+    for (int64_t dy = -r - 1; dy <= r + 1; dy++)
+        for (int64_t dx = -r - 1; dx <= r + 1; dx++) {
+            int64_t d2 = dx * dx + dy * dy;
+            if (d2 > outer) continue;
+            int64_t cov = d2 <= inner ? 255 : r * 256 + 128 - (int64_t)isqrt((uint64_t)d2 << 16);
+            if (cov <= 0) continue;
+            if (cov > 255) cov = 255;
+            plot_cover((int64_t)cx + dx, (int64_t)cy + dy, c, ((uint32_t)cov * opacity + 127) / 255);
+        }
+}
+
+fb_error draw_circle_aa(uint32_t cx, uint32_t cy, uint32_t radius, uint32_t c) {
+    aa_disc(cx, cy, radius, c, 255);
+    return OK;
+}
+
+// Filled circle shaded from `center` at the middle to `edge` at the radius,
+// by each pixel's exact distance from the centre. Covers the same pixels as
+// draw_circle().
+fb_error fill_circle_gradient(uint32_t cx, uint32_t cy, uint32_t radius,
+                              uint32_t center, uint32_t edge) {
+    int64_t r = radius, hw = r;
+    for (int64_t dy = 0; dy <= r; dy++) {
+        while (hw * hw + dy * dy > r * r) hw--;
+        for (int64_t dx = -hw; dx <= hw; dx++) {
+            uint32_t t = 0;
+            if (r) {
+                uint64_t d = isqrt((uint64_t)(dx * dx + dy * dy) << 16);
+                t = (uint32_t)(d * 255 / ((uint64_t)r << 8));
+            }
+            uint32_t c = mix(center, edge, (uint8_t)(t > 255 ? 255 : t));
+            plot((int64_t)cx + dx, (int64_t)cy + dy, c);
+            if (dy) plot((int64_t)cx + dx, (int64_t)cy - dy, c);
+        }
+    }
+    return OK;
+}
+
+// Wu's circle: for each step along the shallow octant, split the exact edge
+// between the two pixels it falls across and mirror into the other seven.
+fb_error draw_circle_outline_aa(uint32_t ucx, uint32_t ucy, uint32_t radius, uint32_t c) {
+    int64_t cx = ucx, cy = ucy, r2 = (int64_t)radius * radius;
+    for (int64_t x = 0; ; x++) {
+        int64_t yf = isqrt((uint64_t)(r2 - x * x) << 16);
+        int64_t y = yf >> 8;
+        uint32_t frac = (uint32_t)(yf & 0xFF);
+        if (x > y) break;
+
+        for (int o = 0; o < 2; o++) {
+            int64_t py = y + o;
+            uint32_t cov = o ? frac : 255 - frac;
+            plot_cover(cx + x, cy + py, c, cov);
+            plot_cover(cx + x, cy - py, c, cov);
+            plot_cover(cx + py, cy + x, c, cov);
+            plot_cover(cx - py, cy + x, c, cov);
+            if (x) {
+                plot_cover(cx - x, cy + py, c, cov);
+                plot_cover(cx - x, cy - py, c, cov);
+                plot_cover(cx + py, cy - x, c, cov);
+                plot_cover(cx - py, cy - x, c, cov);
+            }
+        }
+    }
+    return OK;
+}
+
+static inline void span(int64_t x0, int64_t x1, int64_t y, uint32_t c) {
+    if (y < 0 || x1 < 0) return;
+    if (x0 < 0) x0 = 0;
+    draw_span(x0, x1, y, c);
+}
+
+static void ellipse_points(int64_t cx, int64_t cy, int64_t x, int64_t y,
+                           int fill, uint32_t c) {
+    if (fill) {
+        span(cx - x, cx + x, cy + y, c);
+        if (y) span(cx - x, cx + x, cy - y, c);
+        return;
+    }
+    plot(cx + x, cy + y, c);
+    plot(cx - x, cy + y, c);
+    plot(cx + x, cy - y, c);
+    plot(cx - x, cy - y, c);
+}
+
+// Midpoint ellipse. Region 1 steps x while the slope is shallow, region 2
+// steps y once it is steep. When filling, a row's span is emitted only once
+// its widest x is known.
+static void ellipse(uint32_t ucx, uint32_t ucy, uint32_t urx, uint32_t ury,
+                    int fill, uint32_t c) {
+    int64_t cx = ucx, cy = ucy, rx = urx, ry = ury;
+    if (rx == 0 || ry == 0) {
+        draw_line(cx > rx ? (uint32_t)(cx - rx) : 0, cy > ry ? (uint32_t)(cy - ry) : 0,
+                  (uint32_t)(cx + rx), (uint32_t)(cy + ry), c);
+        return;
+    }
+
+    int64_t rx2 = rx * rx, ry2 = ry * ry;
+    int64_t x = 0, y = ry;
+    int64_t px = 0, py = 2 * rx2 * y;
+
+    int64_t p = ry2 - rx2 * ry + rx2 / 4;
+    while (px < py) {
+        if (!fill) ellipse_points(cx, cy, x, y, 0, c);
+        x++;
+        px += 2 * ry2;
+        if (p < 0) {
+            p += ry2 + px;
+        } else {
+            if (fill) ellipse_points(cx, cy, x - 1, y, 1, c);
+            y--;
+            py -= 2 * rx2;
+            p += ry2 + px - py;
+        }
+    }
+
+    p = ry2 * (2 * x + 1) * (2 * x + 1) / 4 + rx2 * (y - 1) * (y - 1) - rx2 * ry2;
+    while (y >= 0) {
+        ellipse_points(cx, cy, x, y, fill, c);
+        y--;
+        py -= 2 * rx2;
+        if (p > 0) {
+            p += rx2 - py;
+        } else {
+            x++;
+            px += 2 * ry2;
+            p += rx2 - py + px;
+        }
+    }
+}
+
+fb_error draw_ellipse(uint32_t cx, uint32_t cy, uint32_t rx, uint32_t ry, uint32_t c) {
+    ellipse(cx, cy, rx, ry, 0, c);
+    return OK;
+}
+
+fb_error fill_ellipse(uint32_t cx, uint32_t cy, uint32_t rx, uint32_t ry, uint32_t c) {
+    ellipse(cx, cy, rx, ry, 1, c);
+    return OK;
+}
+
+// Angular range for arcs and pies. Angles are in degrees, 0 pointing right
+// and increasing clockwise on screen; end < start wraps through 360.
+typedef struct {
+    int64_t sx, sy, ex, ey;
+    int     full;
+    int     reflex;
+} sweep_t;
+
+static int sweep_init(sweep_t *sw, uint32_t start_deg, uint32_t end_deg) {
+    uint32_t extent = end_deg >= start_deg ? end_deg - start_deg
+                                           : end_deg + 360 - start_deg;
+    sw->full   = extent >= 360;
+    sw->reflex = extent > 180;
+    sw->sx = cos_deg((int32_t)start_deg);
+    sw->sy = sin_deg((int32_t)start_deg);
+    sw->ex = cos_deg((int32_t)end_deg);
+    sw->ey = sin_deg((int32_t)end_deg);
+    return extent > 0;
+}
+
+// A point is inside a sweep of at most 180 degrees when it lies clockwise of
+// the start ray and anticlockwise of the end ray; a larger sweep is everything
+// not strictly inside the complementary one.
+static int sweep_contains(const sweep_t *sw, int64_t dx, int64_t dy) {
+    if (sw->full) return 1;
+    int64_t after_start = sw->sx * dy - sw->sy * dx;
+    int64_t before_end  = dx * sw->ey - dy * sw->ex;
+    if (sw->reflex) return !(after_start < 0 && before_end < 0);
+    return after_start >= 0 && before_end >= 0;
+}
+
+fb_error draw_arc(uint32_t cx, uint32_t cy, uint32_t radius,
+                  uint32_t start_deg, uint32_t end_deg, uint32_t c) {
+    sweep_t sw;
+    if (start_deg > 360 || end_deg > 360) return INVALID_DISPLAY_PARAMS;
+    if (!sweep_init(&sw, start_deg, end_deg)) return OK;
+
+    int64_t x = 0, y = radius, d = 1 - (int64_t)radius;
+    while (x <= y) {
+        int64_t pts[8][2] = {
+            {  x,  y }, { -x,  y }, {  x, -y }, { -x, -y },
+            {  y,  x }, { -y,  x }, {  y, -x }, { -y, -x },
+        };
+        for (int i = 0; i < 8; i++)
+            if (sweep_contains(&sw, pts[i][0], pts[i][1]))
+                plot((int64_t)cx + pts[i][0], (int64_t)cy + pts[i][1], c);
+        if (d < 0) { d += 2 * x + 3; }
+        else       { d += 2 * (x - y) + 5; y--; }
+        x++;
+    }
+    return OK;
+}
+
+fb_error fill_pie(uint32_t cx, uint32_t cy, uint32_t radius,
+                  uint32_t start_deg, uint32_t end_deg, uint32_t c) {
+    sweep_t sw;
+    if (start_deg > 360 || end_deg > 360) return INVALID_DISPLAY_PARAMS;
+    if (!sweep_init(&sw, start_deg, end_deg)) return OK;
+
+    int64_t r = radius;
+    for (int64_t dy = -r; dy <= r; dy++)
+        for (int64_t dx = -r; dx <= r; dx++)
+            if (dx * dx + dy * dy <= r * r && sweep_contains(&sw, dx, dy))
+                plot((int64_t)cx + dx, (int64_t)cy + dy, c);
+    return OK;
+}
+
+// Bresenham on signed coordinates, so curves and polygons may run off-screen.
+static void line(int64_t x0, int64_t y0, int64_t x1, int64_t y1, uint32_t c) {
+    int64_t dx  =  x1 > x0 ? x1 - x0 : x0 - x1;
+    int64_t dy  = -(y1 > y0 ? y1 - y0 : y0 - y1);
+    int64_t sx  = x0 < x1 ? 1 : -1;
+    int64_t sy  = y0 < y1 ? 1 : -1;
+    int64_t err = dx + dy;
+
+    while (1) {
+        plot(x0, y0, c);
+        if (x0 == x1 && y0 == y1) break;
+        int64_t e2 = 2 * err;
+        if (e2 >= dy) { err += dy; x0 += sx; }
+        if (e2 <= dx) { err += dx; y0 += sy; }
+    }
+}
+
+static int64_t cheb(int64_t x0, int64_t y0, int64_t x1, int64_t y1) {
+    int64_t dx = x1 > x0 ? x1 - x0 : x0 - x1;
+    int64_t dy = y1 > y0 ? y1 - y0 : y0 - y1;
+    return dx > dy ? dx : dy;
+}
+
+// Curves are sampled at t = i / n in 10-bit fixed point, with n taken from the
+// length of the control polygon, and the samples joined by straight lines.
+#define BEZIER_ONE 1024
+
+static int64_t bezier_steps(int64_t length) {
+    int64_t n = length / 2;
+    if (n < 1) n = 1;
+    if (n > BEZIER_ONE) n = BEZIER_ONE;
+    return n;
+}
+
+fb_error draw_bezier_quad(int32_t x0, int32_t y0, int32_t x1, int32_t y1,
+                          int32_t x2, int32_t y2, uint32_t c) {
+    int64_t n = bezier_steps(cheb(x0, y0, x1, y1) + cheb(x1, y1, x2, y2));
+    int64_t px = x0, py = y0;
+    for (int64_t i = 1; i <= n; i++) {
+        int64_t t = i * BEZIER_ONE / n, u = BEZIER_ONE - t;
+        int64_t a = u * u, b = 2 * u * t, d = t * t;
+        int64_t half = (int64_t)BEZIER_ONE * BEZIER_ONE / 2;
+        int64_t qx = (a * x0 + b * x1 + d * x2 + half) / ((int64_t)BEZIER_ONE * BEZIER_ONE);
+        int64_t qy = (a * y0 + b * y1 + d * y2 + half) / ((int64_t)BEZIER_ONE * BEZIER_ONE);
+        line(px, py, qx, qy, c);
+        px = qx;
+        py = qy;
+    }
+    return OK;
+}
+
+fb_error draw_bezier_cubic(int32_t x0, int32_t y0, int32_t x1, int32_t y1,
+                           int32_t x2, int32_t y2, int32_t x3, int32_t y3, uint32_t c) {
+    int64_t n = bezier_steps(cheb(x0, y0, x1, y1) + cheb(x1, y1, x2, y2) +
+                             cheb(x2, y2, x3, y3));
+    int64_t one3 = (int64_t)BEZIER_ONE * BEZIER_ONE * BEZIER_ONE;
+    int64_t px = x0, py = y0;
+    for (int64_t i = 1; i <= n; i++) {
+        int64_t t = i * BEZIER_ONE / n, u = BEZIER_ONE - t;
+        int64_t a = u * u * u, b = 3 * u * u * t, d = 3 * u * t * t, e = t * t * t;
+        int64_t qx = (a * x0 + b * x1 + d * x2 + e * x3 + one3 / 2) / one3;
+        int64_t qy = (a * y0 + b * y1 + d * y2 + e * y3 + one3 / 2) / one3;
+        line(px, py, qx, qy, c);
+        px = qx;
+        py = qy;
+    }
+    return OK;
+}
+
+// Bresenham along the major axis, with a run of pixels across the minor axis at
+// every step. The run is stretched by length / major so diagonals come out as
+// wide as straight lines, and consecutive runs overlap so there are no gaps.
+fb_error draw_line_thick(uint32_t x0, uint32_t y0, uint32_t x1, uint32_t y1,
+                         uint32_t c, uint32_t thickness) {
+    if (thickness == 0) return OK;
+    if (thickness == 1) return draw_line(x0, y0, x1, y1, c);
+
+    int64_t dx = x1 > x0 ? (int64_t)x1 - x0 : (int64_t)x0 - x1;
+    int64_t dy = y1 > y0 ? (int64_t)y1 - y0 : (int64_t)y0 - y1;
+    int64_t major = dx > dy ? dx : dy;
+    int64_t len = isqrt((uint64_t)(dx * dx + dy * dy));
+    int64_t n = major ? ((int64_t)thickness * len + major / 2) / major : thickness;
+    int64_t off = (n - 1) / 2;
+
+    int64_t x = x0, y = y0, sx = x0 < x1 ? 1 : -1, sy = y0 < y1 ? 1 : -1;
+    int64_t err = dx - dy;
+    while (1) {
+        if (dx >= dy) {
+            for (int64_t k = 0; k < n; k++) plot(x, y - off + k, c);
+        } else {
+            span(x - off, x - off + n - 1, y, c);
+        }
+        if (x == x1 && y == y1) break;
+        int64_t e2 = 2 * err;
+        if (e2 >= -dy) { err -= dy; x += sx; }
+        if (e2 <= dx)  { err += dx; y += sy; }
+    }
+    return OK;
+}
+
+fb_error draw_polygon(const point_t *points, uint32_t count, uint32_t c) {
+    if (!points) return NULL_POINTER;
+    for (uint32_t i = 0; i < count; i++) {
+        const point_t *a = &points[i], *b = &points[(i + 1) % count];
+        line(a->x, a->y, b->x, b->y, c);
+    }
+    return OK;
+}
+
+// Like draw_polygon() but open: the last point is not joined back to the first.
+fb_error draw_polyline(const point_t *points, uint32_t count, uint32_t c) {
+    if (!points) return NULL_POINTER;
+    if (count == 1) line(points[0].x, points[0].y, points[0].x, points[0].y, c);
+    for (uint32_t i = 1; i < count; i++)
+        line(points[i - 1].x, points[i - 1].y, points[i].x, points[i].y, c);
+    return OK;
+}
+
+typedef struct {
+    int64_t x0, y0, x1, y1;
+} poly_edge_t;
+
+// Row y is sampled through pixel centres (y + 0.5), so an edge is active on
+// rows y0 <= y < y1 and horizontal edges never are. The crossing is returned
+// in 16.16 fixed point. Edges can span the whole int32 range, so the
+// product is taken in 128 bits; the quotient itself always fits.
+static int64_t edge_cross(const poly_edge_t *e, int64_t y) {
+    __int128 num = (__int128)(2 * (y - e->y0) + 1) * (e->x1 - e->x0) * 65536;
+    return e->x0 * 65536 + (int64_t)(num / (2 * (e->y1 - e->y0)));
+}
+
+// Scanline fill with an active edge table. Crossings on each row are sorted
+// and filled pairwise, which is exactly the even-odd rule.
+fb_error fill_polygon(const point_t *points, uint32_t count, uint32_t c) {
+    if (!points) return NULL_POINTER;
+    if (count < 3) return OK;
+
+    poly_edge_t *edges  = malloc((uint64_t)count * (sizeof(poly_edge_t) + sizeof(uint32_t) + sizeof(int64_t)));
+    if (!edges) return NULL_POINTER;
+    uint32_t    *active = (uint32_t *)(edges + count);
+    int64_t     *xs     = (int64_t *)(active + count);
+
+    uint32_t n = 0;
+    for (uint32_t i = 0; i < count; i++) {
+        const point_t *a = &points[i], *b = &points[(i + 1) % count];
+        if (a->y == b->y) continue;
+        poly_edge_t e = a->y < b->y ? (poly_edge_t){ a->x, a->y, b->x, b->y }
+                                    : (poly_edge_t){ b->x, b->y, a->x, a->y };
+        uint32_t j = n++;
+        while (j > 0 && edges[j - 1].y0 > e.y0) {
+            edges[j] = edges[j - 1];
+            j--;
+        }
+        edges[j] = e;
+    }
+    if (n == 0) { free(edges); return OK; }
+
+    int64_t y_end = edges[0].y1;
+    for (uint32_t i = 1; i < n; i++)
+        if (edges[i].y1 > y_end) y_end = edges[i].y1;
+
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+    if (y_end > (int64_t)cy + ch) y_end = (int64_t)cy + ch;
+    int64_t y_start = edges[0].y0 > (int64_t)cy ? edges[0].y0 : (int64_t)cy;
+
+    // Starting at the clip top, the first row picks up every edge that began
+    // above it and drops those that already ended.
+    uint32_t next = 0, n_active = 0;
+    for (int64_t y = y_start; y < y_end; y++) {
+        while (next < n && edges[next].y0 <= y)
+            active[n_active++] = next++;
+
+        uint32_t keep = 0;
+        for (uint32_t i = 0; i < n_active; i++)
+            if (edges[active[i]].y1 > y) active[keep++] = active[i];
+        n_active = keep;
+
+        for (uint32_t i = 0; i < n_active; i++) {
+            int64_t x = edge_cross(&edges[active[i]], y);
+            uint32_t j = i;
+            while (j > 0 && xs[j - 1] > x) {
+                xs[j] = xs[j - 1];
+                j--;
+            }
+            xs[j] = x;
+        }
+
+        for (uint32_t i = 0; i + 1 < n_active; i += 2) {
+            int64_t x0 = (xs[i]     + 32767) >> 16;
+            int64_t x1 = ((xs[i + 1] + 32767) >> 16) - 1;
+            span(x0, x1, y, c);
+        }
+    }
+
+    free(edges);
+    return OK;
+}
+
+// Arrow glyphs are laid out along the pointing direction: `along` runs from
+// the base towards the tip and `across` along the base.
+static void arrow_point(int64_t cx, int64_t cy, arrow_direction dir,
+                        int64_t along, int64_t across, int64_t *x, int64_t *y) {
+    switch (dir) {
+    case ARROW_LEFT: *x = cx - along;  *y = cy + across; break;
+    case ARROW_UP:   *x = cx + across; *y = cy - along;  break;
+    case ARROW_DOWN: *x = cx + across; *y = cy + along;  break;
+    default:         *x = cx + along;  *y = cy + across; break;
+    }
+}
+
+// A filled triangle pointing in `dir`, centred on (cx, cy): the base is
+// size / 2 * 2 + 1 pixels long and the tip sits size / 2 past it, so a right
+// arrow's tip is at cx + size / 2 - size / 4.
+fb_error draw_arrow(uint32_t cx, uint32_t cy, uint32_t size, arrow_direction dir, uint32_t c) {
+    int64_t half = size / 2, base = -(half / 2);
+    for (int64_t k = 0; k <= half; k++) {
+        int64_t x0, y0, x1, y1;
+        arrow_point(cx, cy, dir, base + k, -(half - k), &x0, &y0);
+        arrow_point(cx, cy, dir, base + k,   half - k,  &x1, &y1);
+        line(x0, y0, x1, y1, c);
+    }
+    return OK;
+}
+
+// The outline of draw_arrow() without its base: two one-pixel strokes meeting
+// at the tip.
+fb_error draw_chevron(uint32_t cx, uint32_t cy, uint32_t size, arrow_direction dir, uint32_t c) {
+    int64_t half = size / 2, base = -(half / 2);
+    int64_t x0, y0, tx, ty, x1, y1;
+    arrow_point(cx, cy, dir, base, -half, &x0, &y0);
+    arrow_point(cx, cy, dir, base + half, 0, &tx, &ty);
+    arrow_point(cx, cy, dir, base,  half, &x1, &y1);
+    line(x0, y0, tx, ty, c);
+    line(tx, ty, x1, y1, c);
+    return OK;
+}
+
+// Map every vertex through t, then fill_polygon() the result.
+fb_error fill_polygon_transformed(const point_t *points, uint32_t count,
+                                  const transform_t *t, uint32_t c) {
+    if (!points || !t) return NULL_POINTER;
+    if (count < 3) return OK;
+    point_t *mapped = malloc((uint64_t)count * sizeof(point_t));
+    if (!mapped) return NULL_POINTER;
+    for (uint32_t i = 0; i < count; i++)
+        mapped[i] = transform_apply(t, points[i]);
+    fb_error err = fill_polygon(mapped, count, c);
+    free(mapped);
+    return err;
+}
+
+#define SPINNER_DOTS 8
+
+// A ring of dots around (cx, cy), brightest at the angle `phase` points to
+// (a full turn is 256 steps, 0 pointing up) and fading behind it. Nothing is
+// kept between calls: advance phase every frame to make it spin. The head dot
+// takes c's alpha, or is opaque if c has none.
+fb_error draw_spinner(uint32_t cx, uint32_t cy, uint32_t radius, uint8_t phase, uint32_t c) {
+    int64_t dot = radius / 5 ? radius / 5 : 1;
+    if ((int64_t)radius <= dot) return OK;
+    int64_t ring = radius - dot;
+    int32_t head = phase * 360 / 256;
+    uint32_t base = alpha_of(c) ? alpha_of(c) : 255;
+    for (int i = 0; i < SPINNER_DOTS; i++) {
+        int32_t angle = i * 360 / SPINNER_DOTS;
+        int32_t behind = ((head - angle) % 360 + 360) % 360;
+        uint32_t a = base * (uint32_t)(360 - behind * 7 / 8) / 360;
+        int64_t x = (int64_t)cx + ((int64_t)sin_deg(angle) * ring >> 16);
+        int64_t y = (int64_t)cy - ((int64_t)cos_deg(angle) * ring >> 16);
+        if (x < 0 || y < 0) continue;
+        aa_disc((uint32_t)x, (uint32_t)y, (uint32_t)dot, c, a);
+    }
+    return OK;
+}
@@ -0,0 +1,309 @@
+#include <stdint.h>
+#include <display.h>
+
+// Intersect a destination rectangle with the current clip. Returns 0 when
+// nothing is visible, otherwise the visible span as [x0, x1) x [y0, y1).
+static int clip_dest(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                     uint32_t *x0, uint32_t *y0, uint32_t *x1, uint32_t *y1) {
+    uint32_t cx, cy, cw, ch;
+    clip_bounds(&cx, &cy, &cw, &ch);
+    uint64_t xe = (uint64_t)x + width, ye = (uint64_t)y + height;
+    *x0 = x > cx ? x : cx;
+    *y0 = y > cy ? y : cy;
+    *x1 = xe < (uint64_t)cx + cw ? (uint32_t)xe : cx + cw;
+    *y1 = ye < (uint64_t)cy + ch ? (uint32_t)ye : cy + ch;
+    return *x0 < *x1 && *y0 < *y1;
+}
+
+// Nearest-neighbour scale a src_w x src_h sprite to dst_w x dst_h.
+fb_error draw_sprite_scaled(uint32_t dst_x, uint32_t dst_y, uint32_t src_w, uint32_t src_h,
+                            const uint32_t *pixels, uint32_t dst_w, uint32_t dst_h) {
+    if (!pixels) return NULL_POINTER;
+    if (src_w == 0 || src_h == 0 || dst_w == 0 || dst_h == 0) return OK;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(dst_x, dst_y, dst_w, dst_h, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        uint32_t sy = (uint32_t)((uint64_t)(row - dst_y) * src_h / dst_h);
+        for (uint32_t col = x0; col < x1; col++) {
+            uint32_t sx = (uint32_t)((uint64_t)(col - dst_x) * src_w / dst_w);
+            blend_pixel(col, row, pixels[sy * src_w + sx]);
+        }
+    }
+    return OK;
+}
+
+// Map a destination column to a source coordinate in 8.8 fixed point, aligning
+// pixel centres and clamping to the sprite's edge texels.
+static int64_t source_coord(uint32_t d, uint32_t src, uint32_t dst) {
+    int64_t s = ((int64_t)d * 2 + 1) * src * 256 / ((int64_t)dst * 2) - 128;
+    if (s < 0) s = 0;
+    if (s > ((int64_t)src - 1) * 256) s = ((int64_t)src - 1) * 256;
+    return s;
+}
+
+// Sample four texels with premultiplied alpha so transparent neighbours don't
+// bleed their (meaningless) colour into the edge of an opaque region.
+static uint32_t sample_bilinear(const uint32_t *pixels, uint32_t w, uint32_t h,
+                                int64_t sx, int64_t sy) {
+    uint32_t x0 = (uint32_t)(sx >> 8), y0 = (uint32_t)(sy >> 8);
+    uint32_t x1 = x0 + 1 < w ? x0 + 1 : x0;
+    uint32_t y1 = y0 + 1 < h ? y0 + 1 : y0;
+    uint32_t fx = (uint32_t)(sx & 0xFF), fy = (uint32_t)(sy & 0xFF);
+
+    uint32_t p[4] = {
+        pixels[y0 * w + x0], pixels[y0 * w + x1],
+        pixels[y1 * w + x0], pixels[y1 * w + x1],
+    };
+    uint32_t wgt[4] = {
+        (256 - fx) * (256 - fy), fx * (256 - fy),
+        (256 - fx) * fy,         fx * fy,
+    };
+
+    uint64_t a = 0, r = 0, g = 0, b = 0;
+    for (int i = 0; i < 4; i++) {
+        uint32_t pa = alpha_of(p[i]);
+        a += (uint64_t)pa * wgt[i];
+        r += (uint64_t)red_of(p[i])   * pa / 255 * wgt[i];
+        g += (uint64_t)green_of(p[i]) * pa / 255 * wgt[i];
+        b += (uint64_t)blue_of(p[i])  * pa / 255 * wgt[i];
+    }
+    a >>= 16; r >>= 16; g >>= 16; b >>= 16;
+    if (a == 0) return 0;
+    return rgba((uint8_t)(r * 255 / a), (uint8_t)(g * 255 / a),
+                (uint8_t)(b * 255 / a), (uint8_t)a);
+}
+
+// Bilinear scale; same arguments as draw_sprite_scaled.
+fb_error draw_sprite_scaled_bilinear(uint32_t dst_x, uint32_t dst_y, uint32_t src_w, uint32_t src_h,
+                                     const uint32_t *pixels, uint32_t dst_w, uint32_t dst_h) {
+    if (!pixels) return NULL_POINTER;
+    if (src_w == 0 || src_h == 0 || dst_w == 0 || dst_h == 0) return OK;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(dst_x, dst_y, dst_w, dst_h, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        int64_t sy = source_coord(row - dst_y, src_h, dst_h);
+        for (uint32_t col = x0; col < x1; col++) {
+            int64_t sx = source_coord(col - dst_x, src_w, dst_w);
+            blend_pixel(col, row, sample_bilinear(pixels, src_w, src_h, sx, sy));
+        }
+    }
+    return OK;
+}
+
+// Blit mirrored about the vertical (flip_h) and/or horizontal (flip_v) axis.
+fb_error draw_sprite_flipped(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                             const uint32_t *pixels, int flip_h, int flip_v) {
+    if (!pixels) return NULL_POINTER;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(x, y, width, height, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        uint32_t sy = flip_v ? height - 1 - (row - y) : row - y;
+        for (uint32_t col = x0; col < x1; col++) {
+            uint32_t sx = flip_h ? width - 1 - (col - x) : col - x;
+            blend_pixel(col, row, pixels[sy * width + sx]);
+        }
+    }
+    return OK;
+}
+
+// Rotate clockwise by quarter_turns * 90 degrees; odd turns swap the
+// destination width and height. Only whole texels move, nothing is resampled.
+fb_error draw_sprite_rotated90(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                               const uint32_t *pixels, uint8_t quarter_turns) {
+    if (!pixels) return NULL_POINTER;
+    quarter_turns %= 4;
+
+    uint32_t dw = (quarter_turns & 1) ? height : width;
+    uint32_t dh = (quarter_turns & 1) ? width  : height;
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(x, y, dw, dh, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        uint32_t v = row - y;
+        for (uint32_t col = x0; col < x1; col++) {
+            uint32_t u = col - x, sx, sy;
+            switch (quarter_turns) {
+                case 0:  sx = u;             sy = v;              break;
+                case 1:  sx = v;             sy = height - 1 - u; break;
+                case 2:  sx = width - 1 - u; sy = height - 1 - v; break;
+                default: sx = width - 1 - v; sy = u;              break;
+            }
+            blend_pixel(col, row, pixels[sy * width + sx]);
+        }
+    }
+    return OK;
+}
+
+// Rotate clockwise by any whole number of degrees about the sprite's centre,
+// which is placed at (cx, cy). Each destination pixel centre is mapped back
+// into the sprite and sampled nearest-neighbour; the maths is done on doubled
+// coordinates so pixel centres stay integral.
+fb_error draw_sprite_rotated(int32_t cx, int32_t cy, uint32_t width, uint32_t height,
+                             const uint32_t *pixels, int32_t angle_deg) {
+    if (!pixels) return NULL_POINTER;
+    if (width == 0 || height == 0) return OK;
+
+    int64_t c = cos_deg(angle_deg), s = sin_deg(angle_deg);
+    int64_t ac = c < 0 ? -c : c, as = s < 0 ? -s : s;
+    int64_t ox = (int64_t)cx - width / 2, oy = (int64_t)cy - height / 2;
+
+    // Half extents of the rotated bounding box, rounded up, plus a pixel slack.
+    int64_t hx = ((ac * width + as * height) >> 17) + 2;
+    int64_t hy = ((as * width + ac * height) >> 17) + 2;
+    int64_t mx = ox + width / 2, my = oy + height / 2;
+
+    uint32_t clx, cly, clw, clh;
+    clip_bounds(&clx, &cly, &clw, &clh);
+    int64_t bx0 = mx - hx > (int64_t)clx ? mx - hx : (int64_t)clx;
+    int64_t by0 = my - hy > (int64_t)cly ? my - hy : (int64_t)cly;
+    int64_t bx1 = mx + hx < (int64_t)clx + clw ? mx + hx : (int64_t)clx + clw;
+    int64_t by1 = my + hy < (int64_t)cly + clh ? my + hy : (int64_t)cly + clh;
+
+    for (int64_t py = by0; py < by1; py++) {
+        int64_t ry = 2 * (py - oy) + 1 - height;
+        for (int64_t px = bx0; px < bx1; px++) {
+            int64_t rx = 2 * (px - ox) + 1 - width;
+            int64_t sx = (c * rx + s * ry + (int64_t)width  * 65536) >> 17;
+            int64_t sy = (c * ry - s * rx + (int64_t)height * 65536) >> 17;
+            if (sx < 0 || sy < 0 || sx >= width || sy >= height) continue;
+            blend_pixel((uint32_t)px, (uint32_t)py, pixels[sy * width + sx]);
+        }
+    }
+    return OK;
+}
+
+// Map a destination offset along one axis of a nine-patch to the source: the
+// first `lo` and last `hi` pixels are copied, the span between is stretched.
+static uint32_t nine_patch_coord(uint32_t d, uint32_t dst, uint32_t src, uint32_t lo, uint32_t hi) {
+    if (d < lo) return d;
+    if (d >= dst - hi) return src - (dst - d);
+    return lo + (uint32_t)((uint64_t)(d - lo) * (src - lo - hi) / (dst - lo - hi));
+}
+
+// Draw a src_w x src_h nine-patch image stretched to width x height. The
+// corners keep their size, the edges stretch along their length and the
+// centre both ways. The destination must be at least as large as the insets,
+// and a stretched part must have at least one source pixel.
+fb_error draw_nine_patch(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                         const uint32_t *pixels, uint32_t src_w, uint32_t src_h,
+                         uint32_t left, uint32_t right, uint32_t top, uint32_t bottom) {
+    if (!pixels) return NULL_POINTER;
+    if ((uint64_t)left + right > src_w || (uint64_t)top + bottom > src_h)
+        return INVALID_DISPLAY_PARAMS;
+    if ((uint64_t)left + right > width || (uint64_t)top + bottom > height)
+        return INVALID_DISPLAY_PARAMS;
+    if ((left + right == src_w && left + right < width) ||
+        (top + bottom == src_h && top + bottom < height))
+        return INVALID_DISPLAY_PARAMS;
+    if (width == 0 || height == 0) return OK;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(x, y, width, height, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        uint32_t sy = nine_patch_coord(row - y, height, src_h, top, bottom);
+        for (uint32_t col = x0; col < x1; col++) {
+            uint32_t sx = nine_patch_coord(col - x, width, src_w, left, right);
+            blend_pixel(col, row, pixels[(uint64_t)sy * src_w + sx]);
+        }
+    }
+    return OK;
+}
+
+// Fill width x height by repeating a tile_w x tile_h tile; tiles cut by the
+// right and bottom edges are clipped. (off_x, off_y) shifts the pattern, so
+// stepping it scrolls the texture without moving the rectangle.
+fb_error draw_tiled(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                    const uint32_t *tile, uint32_t tile_w, uint32_t tile_h,
+                    int32_t off_x, int32_t off_y) {
+    if (!tile) return NULL_POINTER;
+    if (tile_w == 0 || tile_h == 0) return INVALID_DISPLAY_PARAMS;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(x, y, width, height, &x0, &y0, &x1, &y1)) return OK;
+
+    uint32_t ox = (uint32_t)(((int64_t)off_x % tile_w + tile_w) % tile_w);
+    uint32_t oy = (uint32_t)(((int64_t)off_y % tile_h + tile_h) % tile_h);
+    for (uint32_t row = y0; row < y1; row++) {
+        const uint32_t *src = tile + (uint64_t)((row - y + oy) % tile_h) * tile_w;
+        uint32_t sx = (x0 - x + ox) % tile_w;
+        for (uint32_t col = x0; col < x1; col++) {
+            blend_pixel(col, row, src[sx]);
+            if (++sx == tile_w) sx = 0;
+        }
+    }
+    return OK;
+}
+
+// Shared by the 1bpp blits: rows are padded to whole bytes, MSB leftmost, and
+// only the visible part of the mask is walked. Off bits are skipped unless
+// `opaque` is set, in which case they are painted with `bg`.
+static fb_error mask_blit(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                          const uint8_t *bits, uint64_t len, uint32_t fg, uint32_t bg, int opaque) {
+    if (!bits) return NULL_POINTER;
+    if (width == 0 || height == 0) return OK;
+    uint64_t row_bytes = ((uint64_t)width + 7) / 8;
+    if (len < row_bytes * height) return BUFFER_TOO_SMALL;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(x, y, width, height, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        const uint8_t *line = bits + (row - y) * row_bytes;
+        for (uint32_t col = x0; col < x1; col++) {
+            uint32_t bit = col - x;
+            if ((line[bit / 8] >> (7 - bit % 8)) & 1)
+                draw_pixel(col, row, fg);
+            else if (opaque)
+                draw_pixel(col, row, bg);
+        }
+    }
+    return OK;
+}
+
+// Draw the set bits of a 1bpp mask in `fg`, leaving the rest untouched.
+fb_error draw_mask(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                   const uint8_t *bits, uint64_t len, uint32_t fg) {
+    return mask_blit(x, y, width, height, bits, len, fg, 0, 0);
+}
+
+// Draw a 1bpp mask fully opaque: set bits in `fg`, clear bits in `bg`.
+fb_error draw_mask_bg(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                      const uint8_t *bits, uint64_t len, uint32_t fg, uint32_t bg) {
+    return mask_blit(x, y, width, height, bits, len, fg, bg, 1);
+}
+
+// Blit an 8bpp image whose bytes index a 256-entry ARGB palette, blending
+// each looked-up colour so palette entries may carry alpha.
+fb_error draw_indexed(uint32_t x, uint32_t y, uint32_t width, uint32_t height,
+                      const uint8_t *indices, uint64_t len, const uint32_t *palette) {
+    if (!indices || !palette) return NULL_POINTER;
+    if (width == 0 || height == 0) return OK;
+    if (len < (uint64_t)width * height) return BUFFER_TOO_SMALL;
+
+    uint32_t x0, y0, x1, y1;
+    if (!clip_dest(x, y, width, height, &x0, &y0, &x1, &y1)) return OK;
+
+    for (uint32_t row = y0; row < y1; row++) {
+        const uint8_t *line = indices + (uint64_t)(row - y) * width;
+        for (uint32_t col = x0; col < x1; col++)
+            blend_pixel(col, row, palette[line[col - x]]);
+    }
+    return OK;
+}
+
+// Fill a 256-entry palette from the first `count` colours; the remaining
+// entries are left fully transparent. `colors` may be 0 only when count is.
+fb_error palette_from(uint32_t *palette, const uint32_t *colors, uint32_t count) {
+    if (!palette || (!colors && count)) return NULL_POINTER;
+    if (count > 256) count = 256;
+    for (uint32_t i = 0; i < 256; i++)
+        palette[i] = i < count ? colors[i] : 0;
+    return OK;
+}
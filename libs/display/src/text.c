@@ -0,0 +1,432 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <display.h>
+
+#define PSF2_MAGIC       0x864AB572
+#define PSF2_HEADER_SIZE 32
+
+typedef struct {
+    uint32_t magic;
+    uint32_t version;
+    uint32_t headersize;
+    uint32_t flags;
+    uint32_t numglyph;
+    uint32_t bytesperglyph;
+    uint32_t height;
+    uint32_t width;
+} psf2_header_t;
+
+// 8x8 public-domain console font covering printable ASCII (0x20..0x7E).
+// Each byte is one row, least significant bit is the leftmost pixel.
+static const uint8_t font8x8[95][8] = {
+    { 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 },  // ' '
+    { 0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00 },  // '!'
+    { 0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 },  // '"'
+    { 0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00 },  // '#'
+    { 0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00 },  // '$'
+    { 0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00 },  // '%'
+    { 0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00 },  // '&'
+    { 0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00 },  // '''
+    { 0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00 },  // '('
+    { 0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00 },  // ')'
+    { 0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00 },  // '*'
+    { 0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00 },  // '+'
+    { 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06 },  // ','
+    { 0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00 },  // '-'
+    { 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00 },  // '.'
+    { 0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00 },  // '/'
+    { 0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00 },  // '0'
+    { 0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00 },  // '1'
+    { 0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00 },  // '2'
+    { 0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00 },  // '3'
+    { 0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00 },  // '4'
+    { 0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00 },  // '5'
+    { 0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00 },  // '6'
+    { 0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00 },  // '7'
+    { 0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00 },  // '8'
+    { 0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00 },  // '9'
+    { 0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00 },  // ':'
+    { 0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06 },  // ';'
+    { 0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00 },  // '<'
+    { 0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00 },  // '='
+    { 0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00 },  // '>'
+    { 0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00 },  // '?'
+    { 0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00 },  // '@'
+    { 0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00 },  // 'A'
+    { 0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00 },  // 'B'
+    { 0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00 },  // 'C'
+    { 0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00 },  // 'D'
+    { 0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00 },  // 'E'
+    { 0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00 },  // 'F'
+    { 0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00 },  // 'G'
+    { 0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00 },  // 'H'
+    { 0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00 },  // 'I'
+    { 0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00 },  // 'J'
+    { 0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00 },  // 'K'
+    { 0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00 },  // 'L'
+    { 0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00 },  // 'M'
+    { 0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00 },  // 'N'
+    { 0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00 },  // 'O'
+    { 0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00 },  // 'P'
+    { 0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00 },  // 'Q'
+    { 0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00 },  // 'R'
+    { 0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00 },  // 'S'
+    { 0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00 },  // 'T'
+    { 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00 },  // 'U'
+    { 0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00 },  // 'V'
+    { 0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00 },  // 'W'
+    { 0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00 },  // 'X'
+    { 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00 },  // 'Y'
+    { 0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00 },  // 'Z'
+    { 0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00 },  // '['
+    { 0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00 },  // '\'
+    { 0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00 },  // ']'
+    { 0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00 },  // '^'
+    { 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF },  // '_'
+    { 0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00 },  // '`'
+    { 0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00 },  // 'a'
+    { 0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00 },  // 'b'
+    { 0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00 },  // 'c'
+    { 0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00 },  // 'd'
+    { 0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00 },  // 'e'
+    { 0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00 },  // 'f'
+    { 0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F },  // 'g'
+    { 0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00 },  // 'h'
+    { 0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00 },  // 'i'
+    { 0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E },  // 'j'
+    { 0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00 },  // 'k'
+    { 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00 },  // 'l'
+    { 0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00 },  // 'm'
+    { 0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00 },  // 'n'
+    { 0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00 },  // 'o'
+    { 0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F },  // 'p'
+    { 0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78 },  // 'q'
+    { 0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00 },  // 'r'
+    { 0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00 },  // 's'
+    { 0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00 },  // 't'
+    { 0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00 },  // 'u'
+    { 0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00 },  // 'v'
+    { 0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00 },  // 'w'
+    { 0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00 },  // 'x'
+    { 0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F },  // 'y'
+    { 0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00 },  // 'z'
+    { 0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00 },  // '{'
+    { 0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00 },  // '|'
+    { 0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00 },  // '}'
+    { 0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 },  // '~'
+};
+
+// Drawn for anything outside printable ASCII.
+static const uint8_t fallback_glyph[8] = {
+    0x7F, 0x41, 0x41, 0x41, 0x41, 0x41, 0x7F, 0x00
+};
+
+static const uint8_t *glyph_for(char ch) {
+    unsigned char c = (unsigned char)ch;
+    if (c < 0x20 || c > 0x7E) return fallback_glyph;
+    return font8x8[c - 0x20];
+}
+
+fb_error draw_char(uint32_t x, uint32_t y, char ch, uint32_t c) {
+    const uint8_t *glyph = glyph_for(ch);
+    for (uint32_t row = 0; row < FONT_HEIGHT; row++)
+        for (uint32_t col = 0; col < FONT_WIDTH; col++)
+            if ((glyph[row] >> col) & 1)
+                draw_pixel(x + col, y + row, c);
+    return OK;
+}
+
+// Draw a string with the built-in font, starting a new line at each '\n'.
+// Returns the width in pixels of the widest line drawn.
+uint32_t draw_text(uint32_t x, uint32_t y, const char *s, uint32_t c) {
+    if (!s) return 0;
+    uint32_t cx = x, cy = y, widest = 0;
+    for (; *s; s++) {
+        if (*s == '\n') {
+            cx  = x;
+            cy += FONT_HEIGHT;
+            continue;
+        }
+        draw_char(cx, cy, *s, c);
+        cx += FONT_WIDTH;
+        if (cx - x > widest) widest = cx - x;
+    }
+    return widest;
+}
+
+// Like draw_text, with `tracking` 1/256ths of a pixel added between glyphs
+// (negative tightens). The pen is kept in 24.8 fixed point and each glyph is
+// placed at its rounded position, so the spacing averages out exactly.
+uint32_t draw_text_tracked(uint32_t x, uint32_t y, const char *s, uint32_t c, int32_t tracking) {
+    if (!s) return 0;
+    int64_t step = (int64_t)FONT_WIDTH * 256 + tracking;
+    int64_t pen = 0;
+    uint32_t cy = y, widest = 0;
+    for (; *s; s++) {
+        if (*s == '\n') {
+            pen = 0;
+            cy += FONT_HEIGHT;
+            continue;
+        }
+        int64_t gx = (int64_t)x + ((pen + 128) >> 8);
+        if (gx >= 0 && gx <= UINT32_MAX) {
+            draw_char((uint32_t)gx, cy, *s, c);
+            if (gx + FONT_WIDTH - x > widest) widest = (uint32_t)(gx + FONT_WIDTH - x);
+        }
+        pen += step;
+    }
+    return widest;
+}
+
+// draw_text() over a copy of itself in `shadow`, offset down and right by
+// `offset` pixels, to keep it legible on a busy background. Returns the width
+// of the main text.
+uint32_t draw_text_shadow(uint32_t x, uint32_t y, const char *s, uint32_t c,
+                          uint32_t shadow, uint32_t offset) {
+    if (!s) return 0;
+    draw_text(x + offset, y + offset, s, shadow);
+    return draw_text(x, y, s, c);
+}
+
+// Draw text inside a box max_width pixels wide, breaking lines at spaces.
+// A run of spaces is kept between words on one line and dropped where the line
+// breaks; a word wider than the box is split across lines. Returns the height
+// in pixels of the lines laid out, the same as measure_text_builtin() for the
+// wrapped result.
+uint32_t draw_text_wrapped(uint32_t x, uint32_t y, uint32_t max_width, const char *s, uint32_t c) {
+    if (!s || !*s) return 0;
+    uint32_t cols = max_width / FONT_WIDTH;
+    if (cols == 0) cols = 1;
+
+    uint32_t row = 0, col = 0;
+    const char *p = s;
+    while (*p) {
+        if (*p == '\n') {
+            row++;
+            col = 0;
+            p++;
+            continue;
+        }
+        uint32_t gap = 0, len = 0;
+        while (p[gap] == ' ') gap++;
+        while (p[gap + len] && p[gap + len] != ' ' && p[gap + len] != '\n') len++;
+        if (len == 0) { p += gap; continue; }
+
+        if ((uint64_t)col + gap + len <= cols) {
+            col += gap;
+        } else if (col > 0) {
+            row++;
+            col = 0;
+        }
+        p += gap;
+
+        while (len) {
+            if (col == cols) { row++; col = 0; }
+            uint32_t n = len < cols - col ? len : cols - col;
+            for (uint32_t i = 0; i < n; i++)
+                draw_char(x + (col + i) * FONT_WIDTH, y + row * FONT_HEIGHT, p[i], c);
+            col += n;
+            len -= n;
+            p   += n;
+        }
+    }
+    return (row + 1) * FONT_HEIGHT;
+}
+
+// Draw s at (tx, centred in r), clipped to r. tx may be negative, in which
+// case the glyphs that would start left of the screen are skipped.
+static fb_error text_in_rect(rect_t r, int64_t tx, uint32_t th, const char *s, uint32_t c) {
+    uint32_t ty = th < r.h ? r.y + (r.h - th) / 2 : r.y;
+    fb_error err = push_clip_rect(r);
+    if (err != OK) return err;
+    if (tx >= 0) {
+        draw_text((uint32_t)tx, ty, s, c);
+        return pop_clip();
+    }
+    for (int64_t gx = tx; *s; s++) {
+        if (*s == '\n') {
+            gx  = tx;
+            ty += FONT_HEIGHT;
+            continue;
+        }
+        if (gx >= 0) draw_char((uint32_t)gx, ty, *s, c);
+        gx += FONT_WIDTH;
+    }
+    return pop_clip();
+}
+
+// Draw s centred both ways in r, cut off at r's edges if it doesn't fit.
+fb_error draw_text_centered(rect_t r, const char *s, uint32_t c) {
+    if (!s) return NULL_POINTER;
+    uint32_t tw, th;
+    measure_text_builtin(s, &tw, &th);
+    return text_in_rect(r, tw < r.w ? r.x + (r.w - tw) / 2 : r.x, th, s, c);
+}
+
+// Draw s against the right edge of r, centred vertically. Text wider than r
+// keeps its end visible and loses its start.
+fb_error draw_text_right(rect_t r, const char *s, uint32_t c) {
+    if (!s) return NULL_POINTER;
+    uint32_t tw, th;
+    measure_text_builtin(s, &tw, &th);
+    return text_in_rect(r, (int64_t)r.x + r.w - tw, th, s, c);
+}
+
+// Measure the inked columns of one glyph cell so the font can be drawn
+// proportionally: the glyph is pulled left by its blank leading columns and
+// advances by its ink width plus one pixel of spacing. Blank glyphs such as
+// the space keep no ink and advance by half a cell.
+static glyph_t measure_glyph(const uint8_t *bits, uint32_t width, uint32_t height,
+                             uint32_t row_bytes) {
+    uint32_t lo = width, hi = 0;
+    for (uint32_t row = 0; row < height; row++) {
+        const uint8_t *rowbytes = bits + row * row_bytes;
+        for (uint32_t col = 0; col < width; col++) {
+            if (!((rowbytes[col / 8] >> (7 - col % 8)) & 1)) continue;
+            if (col < lo) lo = col;
+            if (col > hi) hi = col;
+        }
+    }
+
+    glyph_t g = { .height = (uint8_t)height };
+    if (lo > hi) {
+        g.advance = (uint8_t)(width / 2 ? width / 2 : 1);
+        return g;
+    }
+    g.width     = (uint8_t)(hi - lo + 1);
+    g.bearing_x = (int8_t)-(int32_t)lo;
+    g.advance   = (uint8_t)(g.width + 1);
+    return g;
+}
+
+// Parse a PSF2 console font, measuring each glyph for proportional drawing.
+// The glyph bitmaps are not copied, so `data` must stay alive for as long as
+// the font is used; release it with font_free().
+font_error font_from_psf2(font_t *font, const uint8_t *data, uint32_t len) {
+    if (!font || !data) return FONT_NULL_POINTER;
+    if (len < PSF2_HEADER_SIZE) return FONT_TRUNCATED;
+
+    const psf2_header_t *h = (const psf2_header_t *)data;
+    if (h->magic != PSF2_MAGIC) return FONT_BAD_MAGIC;
+    if (h->headersize < PSF2_HEADER_SIZE || h->headersize > len) return FONT_BAD_HEADER;
+    // Cells wider than 128 could not pull their ink back with an int8 bearing.
+    if (h->width == 0 || h->height == 0 || h->width > 128 || h->height > 255)
+        return FONT_BAD_HEADER;
+    uint32_t row_bytes = (h->width + 7) / 8;
+    if (h->numglyph == 0 || h->bytesperglyph != h->height * row_bytes)
+        return FONT_BAD_HEADER;
+    if ((uint64_t)h->numglyph * h->bytesperglyph > len - h->headersize)
+        return FONT_TRUNCATED;
+
+    glyph_t *glyphs = malloc((uint64_t)h->numglyph * sizeof(glyph_t));
+    if (!glyphs) return FONT_NO_MEMORY;
+    const uint8_t *bitmap = data + h->headersize;
+    for (uint32_t i = 0; i < h->numglyph; i++) {
+        glyphs[i] = measure_glyph(bitmap + i * h->bytesperglyph, h->width,
+                                  h->height, row_bytes);
+        glyphs[i].offset = i * h->bytesperglyph;
+    }
+
+    font->num_glyphs = h->numglyph;
+    font->height     = h->height;
+    font->row_bytes  = row_bytes;
+    font->glyphs     = glyphs;
+    font->bitmap     = bitmap;
+    return FONT_OK;
+}
+
+void font_free(font_t *font) {
+    if (!font) return;
+    free((void *)font->glyphs);
+    font->glyphs     = 0;
+    font->num_glyphs = 0;
+}
+
+// Glyph rows span the whole font cell, padded to whole bytes, most
+// significant bit leftmost; the bearing moves the cell so its ink starts
+// where the glyph's metrics say.
+static void draw_font_glyph(uint32_t x, uint32_t y, const font_t *font,
+                            const glyph_t *g, uint32_t c) {
+    const uint8_t *bits = font->bitmap + g->offset;
+    int64_t gx = (int64_t)x + g->bearing_x;
+    int64_t gy = (int64_t)y + g->bearing_y;
+
+    for (uint32_t row = 0; row < g->height; row++) {
+        const uint8_t *rowbytes = bits + row * font->row_bytes;
+        for (uint32_t col = 0; col < font->row_bytes * 8; col++) {
+            if (!((rowbytes[col / 8] >> (7 - col % 8)) & 1)) continue;
+            int64_t px = gx + col, py = gy + row;
+            if (px >= 0 && py >= 0)
+                draw_pixel((uint32_t)px, (uint32_t)py, c);
+        }
+    }
+}
+
+// Characters the font doesn't cover fall back to '?', or are skipped if even
+// that is missing.
+static const glyph_t *font_glyph(const font_t *font, char ch) {
+    uint32_t cp = (unsigned char)ch;
+    if (cp >= font->num_glyphs) cp = '?';
+    if (cp >= font->num_glyphs) return 0;
+    return &font->glyphs[cp];
+}
+
+// Like draw_text, but each glyph advances the pen by its own width.
+uint32_t draw_text_font(uint32_t x, uint32_t y, const char *s,
+                        const font_t *font, uint32_t c) {
+    if (!s || !font || !font->glyphs) return 0;
+    uint32_t cx = x, cy = y, widest = 0;
+    for (; *s; s++) {
+        if (*s == '\n') {
+            cx  = x;
+            cy += font->height;
+            continue;
+        }
+        const glyph_t *g = font_glyph(font, *s);
+        if (!g) continue;
+        draw_font_glyph(cx, cy, font, g, c);
+        cx += g->advance;
+        if (cx - x > widest) widest = cx - x;
+    }
+    return widest;
+}
+
+// Size of the box draw_text_font() would cover: the widest line and the
+// height of every line, counting a trailing '\n' as starting an empty one.
+void measure_text(const char *s, const font_t *font, uint32_t *width, uint32_t *height) {
+    uint32_t w = 0, h = 0, line = 0;
+    if (s && *s && font && font->glyphs) {
+        h = font->height;
+        for (; *s; s++) {
+            if (*s == '\n') {
+                line = 0;
+                h   += font->height;
+                continue;
+            }
+            const glyph_t *g = font_glyph(font, *s);
+            if (g) line += g->advance;
+            if (line > w) w = line;
+        }
+    }
+    if (width)  *width  = w;
+    if (height) *height = h;
+}
+
+// measure_text() for the built-in fixed-width font.
+void measure_text_builtin(const char *s, uint32_t *width, uint32_t *height) {
+    uint32_t w = 0, h = 0, line = 0;
+    if (s && *s) {
+        h = FONT_HEIGHT;
+        for (; *s; s++) {
+            if (*s == '\n') {
+                line = 0;
+                h   += FONT_HEIGHT;
+                continue;
+            }
+            line += FONT_WIDTH;
+            if (line > w) w = line;
+        }
+    }
+    if (width)  *width  = w;
+    if (height) *height = h;
+}
@@ -0,0 +1,76 @@
+#include <stdint.h>
+#include <display.h>
+
+// sin(0..90 degrees) in 16.16 fixed point; the other quadrants are mirrored.
+static const int32_t sin_table[91] = {
+        0,  1144,  2287,  3430,  4572,  5712,  6850,  7987,
+     9121, 10252, 11380, 12505, 13626, 14742, 15855, 16962,
+    18064, 19161, 20252, 21336, 22415, 23486, 24550, 25607,
+    26656, 27697, 28729, 29753, 30767, 31772, 32768, 33754,
+    34729, 35693, 36647, 37590, 38521, 39441, 40348, 41243,
+    42126, 42995, 43852, 44695, 45525, 46341, 47143, 47930,
+    48703, 49461, 50203, 50931, 51643, 52339, 53020, 53684,
+    54332, 54963, 55578, 56175, 56756, 57319, 57865, 58393,
+    58903, 59396, 59870, 60326, 60764, 61183, 61584, 61966,
+    62328, 62672, 62997, 63303, 63589, 63856, 64104, 64332,
+    64540, 64729, 64898, 65048, 65177, 65287, 65376, 65446,
+    65496, 65526, 65536,
+};
+
+// Sine of a whole number of degrees, any range, in 16.16 fixed point.
+int32_t sin_deg(int32_t deg) {
+    deg %= 360;
+    if (deg < 0) deg += 360;
+    if (deg <= 90)  return  sin_table[deg];
+    if (deg <= 180) return  sin_table[180 - deg];
+    if (deg <= 270) return -sin_table[deg - 180];
+    return -sin_table[360 - deg];
+}
+
+int32_t cos_deg(int32_t deg) {
+    return sin_deg(deg + 90);
+}
+
+#define FIX_ONE 65536
+
+transform_t transform_identity(void) {
+    return (transform_t){ FIX_ONE, 0, 0, FIX_ONE, 0, 0 };
+}
+
+transform_t transform_translate(int32_t dx, int32_t dy) {
+    return (transform_t){ FIX_ONE, 0, 0, FIX_ONE, dx, dy };
+}
+
+// Scale factors are 16.16 fixed point: 65536 keeps the size, 2 * 65536
+// doubles it.
+transform_t transform_scale(int32_t sx, int32_t sy) {
+    return (transform_t){ sx, 0, 0, sy, 0, 0 };
+}
+
+// Rotation about the origin, clockwise on screen like the arc angles.
+transform_t transform_rotate(int32_t deg) {
+    int32_t s = sin_deg(deg), c = cos_deg(deg);
+    return (transform_t){ c, -s, s, c, 0, 0 };
+}
+
+static inline int32_t fix_mul(int32_t x, int32_t y) {
+    return (int32_t)(((int64_t)x * y + FIX_ONE / 2) >> 16);
+}
+
+// The transform that applies `inner` first and then `outer`.
+transform_t transform_multiply(const transform_t *outer, const transform_t *inner) {
+    const transform_t *o = outer, *i = inner;
+    point_t t = transform_apply(o, (point_t){ i->tx, i->ty });
+    return (transform_t){
+        fix_mul(o->a, i->a) + fix_mul(o->b, i->c), fix_mul(o->a, i->b) + fix_mul(o->b, i->d),
+        fix_mul(o->c, i->a) + fix_mul(o->d, i->c), fix_mul(o->c, i->b) + fix_mul(o->d, i->d),
+        t.x, t.y,
+    };
+}
+
+// Map a point, rounding to the nearest pixel.
+point_t transform_apply(const transform_t *t, point_t p) {
+    int64_t x = (int64_t)t->a * p.x + (int64_t)t->b * p.y + FIX_ONE / 2;
+    int64_t y = (int64_t)t->c * p.x + (int64_t)t->d * p.y + FIX_ONE / 2;
+    return (point_t){ (int32_t)(x >> 16) + t->tx, (int32_t)(y >> 16) + t->ty };
+}
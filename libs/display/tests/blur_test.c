@@ -0,0 +1,98 @@
+#include "check.h"
+
+// With blur == 0 the shadow is the offset rectangle itself, half-opaque
+// black over whatever is underneath, clipped where it runs off the screen.
+static void test_shadow_without_blur(void) {
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(draw_shadow(10, 10, 20, 20, 4, 0) == OK);
+
+    uint32_t shade = blend_colors(COLOR_WHITE, COLOR_BLACK, 128);
+    uint32_t wrong = 0;
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++)
+            if (read_pixel(x, y) != (x >= 14 && y >= 14 ? shade : COLOR_WHITE)) wrong++;
+    CHECK(wrong == 0);
+}
+
+// Blurring a single white pixel spreads it over its neighbours, leaves
+// pixels beyond the radius dark and keeps the total brightness, give or take
+// rounding.
+static void test_blur_single_pixel(void) {
+    reset();
+    draw_pixel(10, 10, COLOR_WHITE);
+    CHECK(blur_region(4, 4, 13, 13, 1) == OK);
+    CHECK(red_of(read_pixel(9, 9)) > 0);
+    CHECK(red_of(read_pixel(11, 10)) > 0);
+    CHECK(read_pixel(10, 10) != COLOR_WHITE);
+    CHECK(red_of(read_pixel(12, 10)) == 0);
+
+    uint32_t total = 0;
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++)
+            total += red_of(read_pixel(x, y));
+    CHECK(total >= 255 - 9 && total <= 255 + 9);
+}
+
+// Walking out from the middle of a blurred shadow, each pixel is at least as
+// light as the one before: the shadow fades with distance and never rings.
+static void test_shadow_fades_outward(void) {
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(draw_shadow(6, 6, 10, 10, 0, 4) == OK);
+
+    uint32_t prev = 0, darker = 0;
+    for (uint32_t x = 11; x < TEST_W; x++) {
+        uint32_t v = red_of(read_pixel(x, 11));
+        if (v < prev) darker++;
+        prev = v;
+    }
+    CHECK(darker == 0);
+    CHECK(red_of(read_pixel(11, 11)) < red_of(read_pixel(17, 11)));
+    CHECK(red_of(read_pixel(17, 11)) < red_of(read_pixel(19, 11)));
+    CHECK(read_pixel(20, 11) == COLOR_WHITE);
+}
+
+// Over a red/blue split the frosted panel mixes the two halves near the seam,
+// so it is not a flat fill, lays its tint over everything and leaves the
+// screen outside the panel alone.
+static void test_frosted_panel(void) {
+    reset();
+    draw_rect(0, 0, TEST_W / 2, TEST_H, COLOR_RED);
+    draw_rect(TEST_W / 2, 0, TEST_W / 2, TEST_H, COLOR_BLUE);
+    CHECK(frosted_panel(4, 4, 24, 24, COLOR_WHITE, 64) == OK);
+
+    uint32_t seam = read_pixel(TEST_W / 2, 16);
+    CHECK(red_of(seam) > 64 && blue_of(seam) > 64);
+    CHECK(read_pixel(4, 16) != read_pixel(27, 16));
+    CHECK(green_of(read_pixel(4, 16)) > 0);
+    CHECK(read_pixel(3, 16) == COLOR_RED);
+    CHECK(read_pixel(28, 16) == COLOR_BLUE);
+}
+
+// An inner shadow darkens the rectangle's corners most, leaves its middle
+// and everything outside it alone, and does nothing at blur 0.
+static void test_inner_shadow(void) {
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(draw_inner_shadow(4, 4, 20, 20, 0, COLOR_BLACK) == OK);
+    CHECK(count_color(COLOR_WHITE) == TEST_W * TEST_H);
+
+    CHECK(draw_inner_shadow(4, 4, 20, 20, 4, COLOR_BLACK) == OK);
+    CHECK(read_pixel(4, 4) == COLOR_BLACK);
+    CHECK(read_pixel(23, 23) == COLOR_BLACK);
+    CHECK(red_of(read_pixel(5, 5)) < red_of(read_pixel(7, 7)));
+    CHECK(red_of(read_pixel(6, 6)) < 255);
+    CHECK(read_pixel(14, 14) == COLOR_WHITE);
+    CHECK(read_pixel(14, 8) == COLOR_WHITE);
+    CHECK(read_pixel(3, 4) == COLOR_WHITE);
+    CHECK(read_pixel(24, 23) == COLOR_WHITE);
+}
+
+void blur_tests(void) {
+    test_shadow_without_blur();
+    test_blur_single_pixel();
+    test_shadow_fades_outward();
+    test_frosted_panel();
+    test_inner_shadow();
+}
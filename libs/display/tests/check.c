@@ -0,0 +1,29 @@
+#include "check.h"
+
+uint32_t test_fb[TEST_W * TEST_H];
+int      test_failures = 0;
+
+void reset(void) {
+    init_display_with(test_fb, TEST_W, TEST_H, TEST_W * 4);
+    set_antialias(0);
+    set_global_alpha(255);
+    enable_dirty_tracking(0);
+}
+
+uint32_t count_color(uint32_t color) {
+    uint32_t n = 0;
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++)
+        if (test_fb[i] == color) n++;
+    return n;
+}
+
+int run_suites(const char *name, void (*const *suites)(void), uint32_t count) {
+    for (uint32_t i = 0; i < count; i++)
+        suites[i]();
+    if (test_failures) {
+        printf("%s: %d check(s) failed\n", name, test_failures);
+        return 1;
+    }
+    printf("%s: all checks passed\n", name);
+    return 0;
+}
@@ -0,0 +1,30 @@
+#pragma once
+#include <stdint.h>
+#include <stdio.h>
+#include <display.h>
+
+// Shared by the host test programs. Each suite is a function that runs its
+// checks against a small screen set up by reset(); a failed CHECK reports
+// the line and the program carries on.
+
+#define TEST_W 32
+#define TEST_H 32
+
+extern uint32_t test_fb[TEST_W * TEST_H];
+extern int      test_failures;
+
+#define CHECK(cond) do {                                                    \
+        if (!(cond)) {                                                      \
+            printf("%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+            test_failures++;                                                \
+        }                                                                   \
+    } while (0)
+
+// A fresh, black TEST_W x TEST_H screen with default drawing state.
+void reset(void);
+
+// How many pixels of the test screen are exactly `color`.
+uint32_t count_color(uint32_t color);
+
+// Run the named suites and report; returns the process exit status.
+int run_suites(const char *name, void (*const *suites)(void), uint32_t count);
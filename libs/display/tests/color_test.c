@@ -0,0 +1,136 @@
+#include "check.h"
+
+// Blending stores opaque pixels, so the same visible colour reads back the
+// same whether it was drawn directly or composited.
+static void test_blend_alpha(void) {
+    reset();
+    blend_pixel(0, 0, 0x80FFFFFF);
+    CHECK(read_pixel(0, 0) == 0xFF808080);
+    blend_pixel(1, 0, 0x00FFFFFF);
+    CHECK(read_pixel(1, 0) == 0);
+    blend_pixel(2, 0, color(10, 20, 30));
+    CHECK(read_pixel(2, 0) == color(10, 20, 30));
+    CHECK(blend_colors(0, color(255, 0, 0), 255) == color(255, 0, 0));
+    CHECK(alpha_of(blend_colors(color(1, 2, 3), color(4, 5, 6), 100)) == 0xFF);
+    CHECK(rgba(1, 2, 3, 4) == 0x04010203);
+    CHECK(color(1, 2, 3) == 0xFF010203);
+}
+
+// The primaries sit at 0, 120 and 240 degrees with full saturation and
+// value, and converting back gives the same colour.
+static void test_hsv_primaries(void) {
+    static const struct { uint32_t rgb; uint16_t h; } primaries[] = {
+        { COLOR_RED, 0 }, { COLOR_LIME, 120 }, { COLOR_BLUE, 240 },
+    };
+    for (uint32_t i = 0; i < 3; i++) {
+        uint16_t h = 999;
+        uint8_t  s = 0, v = 0;
+        rgb_to_hsv(primaries[i].rgb, &h, &s, &v);
+        CHECK(h == primaries[i].h);
+        CHECK(s == 255);
+        CHECK(v == 255);
+        CHECK(hsv(h, s, v) == primaries[i].rgb);
+    }
+
+    uint16_t h;
+    uint8_t  s, v;
+    rgb_to_hsv(COLOR_GRAY, &h, &s, &v);
+    CHECK(s == 0 && v == 0x80);
+    CHECK(hsv(h, s, v) == COLOR_GRAY);
+}
+
+// Each mode on a known pair at full alpha; add and subtract saturate per
+// channel, and zero alpha leaves the background whatever the mode.
+static void test_blend_modes(void) {
+    uint32_t grey = color(0x80, 0x80, 0x80);
+    CHECK(blend_colors_mode(grey, grey, 255, BLEND_NORMAL) == grey);
+    CHECK(blend_colors_mode(grey, grey, 255, BLEND_MULTIPLY) == color(0x40, 0x40, 0x40));
+    CHECK(blend_colors_mode(grey, grey, 255, BLEND_SCREEN) == color(0xC0, 0xC0, 0xC0));
+    CHECK(blend_colors_mode(grey, grey, 255, BLEND_ADD) == COLOR_WHITE);
+    CHECK(blend_colors_mode(grey, grey, 255, BLEND_SUBTRACT) == COLOR_BLACK);
+
+    uint32_t bg = color(0x80, 0x40, 0x20), fg = color(0x40, 0xC0, 0xFF);
+    CHECK(blend_colors_mode(bg, fg, 255, BLEND_ADD) == color(0xC0, 0xFF, 0xFF));
+    CHECK(blend_colors_mode(bg, fg, 255, BLEND_SUBTRACT) == color(0x40, 0x00, 0x00));
+    CHECK(blend_colors_mode(bg, fg, 0, BLEND_MULTIPLY) == bg);
+}
+
+// The channel helpers hit their end points exactly, round the midpoint to
+// 0x80 and saturate instead of wrapping.
+static void test_channel_helpers(void) {
+    CHECK(darken(COLOR_WHITE, 255) == COLOR_BLACK);
+    CHECK(darken(COLOR_WHITE, 0) == COLOR_WHITE);
+    CHECK(lighten(COLOR_BLACK, 255) == COLOR_WHITE);
+    CHECK(mix(COLOR_BLACK, COLOR_WHITE, 128) == color(0x80, 0x80, 0x80));
+    CHECK(mix(COLOR_BLACK, COLOR_WHITE, 0) == COLOR_BLACK);
+    CHECK(mix(COLOR_BLACK, COLOR_WHITE, 255) == COLOR_WHITE);
+    CHECK(mix(COLOR_WHITE, COLOR_BLACK, 128) == color(0x7F, 0x7F, 0x7F));
+    CHECK(scale_color(color(0x80, 0x40, 0xC0), 3, 2) == color(0xC0, 0x60, 0xFF));
+    CHECK(scale_color(COLOR_CORAL, 1, 0) == COLOR_CORAL);
+}
+
+// Both hex forms parse to the same opaque colour, in either case; anything
+// malformed is refused and leaves the output alone.
+static void test_from_hex(void) {
+    uint32_t c = 0;
+    CHECK(color_from_hex("#ff0000", &c) && c == color(0xFF, 0, 0));
+    c = 0;
+    CHECK(color_from_hex("#f00", &c) && c == color(0xFF, 0, 0));
+    CHECK(color_from_hex("#1A2b3C", &c) && c == color(0x1A, 0x2B, 0x3C));
+    CHECK(color_from_hex("#abc", &c) && c == color(0xAA, 0xBB, 0xCC));
+
+    c = 1234;
+    CHECK(!color_from_hex("#zzz", &c));
+    CHECK(!color_from_hex("ff0000", &c));
+    CHECK(!color_from_hex("#ff00", &c));
+    CHECK(!color_from_hex("#ff000000", &c));
+    CHECK(!color_from_hex("#", &c));
+    CHECK(!color_from_hex(0, &c));
+    CHECK(c == 1234);
+}
+
+// Half-transparent white plotted over black gives mid grey; plots outside
+// the screen or the clip are refused.
+static void test_pixel_blended(void) {
+    reset();
+    draw_rect(0, 0, 4, 4, COLOR_BLACK);
+    CHECK(draw_pixel_blended(1, 1, 0x80FFFFFF) == OK);
+    CHECK(read_pixel(1, 1) == 0xFF808080);
+    CHECK(draw_pixel_blended(1, 1, 0x00FF0000) == OK);
+    CHECK(read_pixel(1, 1) == 0xFF808080);
+    CHECK(draw_pixel_blended(TEST_W, 0, 0x80FFFFFF) == FAILED_TO_DRAW);
+
+    CHECK(push_clip(2, 2, 2, 2) == OK);
+    CHECK(draw_pixel_blended(1, 2, 0x80FFFFFF) == FAILED_TO_DRAW);
+    CHECK(pop_clip() == OK);
+    CHECK(read_pixel(1, 2) == COLOR_BLACK);
+}
+
+// At global alpha 128 an opaque white rectangle over black comes out at half
+// intensity; flood_fill() writes its colour exactly whatever the global
+// alpha, and leaves the setting as it found it.
+static void test_global_alpha(void) {
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_BLACK);
+    CHECK(set_global_alpha(128) == 255);
+    CHECK(draw_rect(2, 2, 6, 4, COLOR_WHITE) == OK);
+    CHECK(read_pixel(2, 2) == 0xFF808080);
+    CHECK(count_color(0xFF808080) == 6 * 4);
+
+    draw_rect_outline(10, 10, 8, 8, 1, COLOR_WHITE);
+    CHECK(set_global_alpha(128) == 128);
+    CHECK(flood_fill(13, 13, COLOR_RED) == OK);
+    CHECK(count_color(COLOR_RED) == 6 * 6);
+    CHECK(set_global_alpha(255) == 128);
+    CHECK(read_pixel(0, 0) == COLOR_BLACK);
+}
+
+void color_tests(void) {
+    test_blend_alpha();
+    test_hsv_primaries();
+    test_blend_modes();
+    test_channel_helpers();
+    test_from_hex();
+    test_pixel_blended();
+    test_global_alpha();
+}
@@ -0,0 +1,63 @@
+#include "check.h"
+#include <string.h>
+
+// SGR colour escapes select the ANSI palette as opaque pixels, and a
+// character cell written afterwards is painted in them.
+static void test_sgr_colors(void) {
+    reset();
+    text_cursor_t tc;
+    text_cursor_init(&tc, 0, 0, COLOR_WHITE, COLOR_BLACK);
+    CHECK(text_cursor_write(&tc, "\x1b[32;41m ") == OK);
+    CHECK(tc.fg == 0xFF00AA00);
+    CHECK(tc.bg == 0xFFAA0000);
+    CHECK(read_pixel(0, 0) == 0xFFAA0000);
+
+    CHECK(text_cursor_write(&tc, "\x1b[0m") == OK);
+    CHECK(tc.fg == COLOR_WHITE);
+    CHECK(tc.bg == COLOR_BLACK);
+}
+
+// The screen is four cells wide, so a six-character string wraps after the
+// fourth and the last two cells land at the start of the next row.
+static void test_cursor_wraps(void) {
+    reset();
+    text_cursor_t tc;
+    text_cursor_init(&tc, 0, 0, COLOR_WHITE, COLOR_BLUE);
+    CHECK(text_cursor_write(&tc, "ABCDEF") == OK);
+    CHECK(tc.x == 2 * FONT_WIDTH);
+    CHECK(tc.y == FONT_HEIGHT);
+
+    uint32_t row1_left = 0, row1_right = 0;
+    for (uint32_t y = FONT_HEIGHT; y < 2 * FONT_HEIGHT; y++)
+        for (uint32_t x = 0; x < TEST_W; x++)
+            if (read_pixel(x, y) != 0) {
+                if (x < 2 * FONT_WIDTH) row1_left++;
+                else row1_right++;
+            }
+    CHECK(row1_left == 2 * FONT_WIDTH * FONT_HEIGHT);
+    CHECK(row1_right == 0);
+}
+
+// A formatted integer is drawn with the same glyphs as its digits written
+// out by hand.
+static void test_printf_integer(void) {
+    static uint32_t expected[TEST_W * TEST_H];
+    text_cursor_t tc;
+    reset();
+    text_cursor_init(&tc, 0, 8, COLOR_WHITE, COLOR_BLACK);
+    CHECK(text_cursor_write(&tc, "-42") == OK);
+    memcpy(expected, test_fb, sizeof(expected));
+
+    reset();
+    text_cursor_init(&tc, 0, 8, COLOR_WHITE, COLOR_BLACK);
+    CHECK(text_cursor_printf(&tc, "%d", -42) == OK);
+    CHECK(tc.x == 3 * FONT_WIDTH);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+    CHECK(count_color(COLOR_WHITE) > 0);
+}
+
+void console_tests(void) {
+    test_sgr_colors();
+    test_cursor_wraps();
+    test_printf_integer();
+}
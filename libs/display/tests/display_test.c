@@ -0,0 +1,547 @@
+#include "check.h"
+#include <string.h>
+
+// swap_buffers() steps the framebuffer by its pitch: with rows wider than
+// the visible width, each row lands at y * pitch and the padding between
+// rows is left alone.
+static void test_pitch_wider_than_width(void) {
+    enum { W = 4, H = 3, PITCH_PX = 7 };
+    static uint32_t fb[PITCH_PX * H];
+    for (uint32_t i = 0; i < PITCH_PX * H; i++) fb[i] = 0xDEADBEEF;
+
+    CHECK(init_display_with(fb, W, H, PITCH_PX * 4) == OK);
+    CHECK(init_back_buffer() == OK);
+    for (uint32_t y = 0; y < H; y++)
+        draw_rect(0, y, W, 1, color(0, 0, (uint8_t)(y + 1)));
+    CHECK(swap_buffers() == OK);
+
+    for (uint32_t y = 0; y < H; y++) {
+        for (uint32_t x = 0; x < W; x++)
+            CHECK((fb[y * PITCH_PX + x] & 0xFFFFFF) == y + 1);
+        for (uint32_t x = W; x < PITCH_PX; x++)
+            CHECK(fb[y * PITCH_PX + x] == 0xDEADBEEF);
+    }
+}
+
+// present_diff() sends the whole first frame, nothing when the frame is
+// unchanged, and exactly the changed pixels otherwise, whether or not they
+// fall on a word boundary.
+static void test_present_diff(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_BLUE);
+
+    uint64_t written = 1234;
+    CHECK(present_diff(&written) == OK);
+    CHECK(written == TEST_W * TEST_H);
+    CHECK(test_fb[0] == COLOR_BLUE);
+    CHECK(present_diff(&written) == OK);
+    CHECK(written == 0);
+
+    draw_rect(4, 4, 2, 1, COLOR_RED);
+    draw_rect(9, 7, 1, 1, COLOR_RED);
+    draw_rect(TEST_W - 1, TEST_H - 1, 1, 1, COLOR_RED);
+    CHECK(present_diff(&written) == OK);
+    CHECK(written == 4);
+    CHECK(test_fb[4 * TEST_W + 4] == COLOR_RED);
+    CHECK(test_fb[4 * TEST_W + 5] == COLOR_RED);
+    CHECK(test_fb[4 * TEST_W + 6] == COLOR_BLUE);
+    CHECK(test_fb[7 * TEST_W + 9] == COLOR_RED);
+    CHECK(test_fb[TEST_W * TEST_H - 1] == COLOR_RED);
+    CHECK(present_diff(&written) == OK);
+    CHECK(written == 0);
+}
+
+// Only the one kernel framebuffer can be enumerated; any other index is
+// rejected before the kernel is asked for it.
+static void test_display_index(void) {
+    CHECK(display_count() == 1);
+    CHECK(init_display_nth(1) == OUT_OF_BOUNDS);
+    CHECK(init_display_nth(UINT32_MAX) == OUT_OF_BOUNDS);
+}
+
+static void fill_white(void *calls) {
+    (*(int *)calls)++;
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+}
+
+// with_display() runs the callback against display 0; asking for display 1
+// is refused without calling it, so display 0 is left untouched.
+static void test_with_display(void) {
+    reset();
+    int calls = 0;
+    CHECK(with_display(1, fill_white, &calls) == OUT_OF_BOUNDS);
+    CHECK(calls == 0);
+    CHECK(read_pixel(0, 0) == 0);
+
+    CHECK(with_display(0, fill_white, &calls) == OK);
+    CHECK(calls == 1);
+    CHECK(read_pixel(TEST_W - 1, TEST_H - 1) == COLOR_WHITE);
+    CHECK(with_display(0, 0, 0) == NULL_POINTER);
+}
+
+// Nested clips intersect: with two overlapping clips pushed, only the
+// overlap takes writes, and popping them restores the whole screen.
+static void test_clip_stack(void) {
+    reset();
+    CHECK(push_clip(2, 2, 10, 10) == OK);
+    CHECK(push_clip(6, 6, 10, 10) == OK);
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(count_color(COLOR_WHITE) == 6 * 6);
+    CHECK(read_pixel(6, 6) == COLOR_WHITE);
+    CHECK(read_pixel(11, 11) == COLOR_WHITE);
+    CHECK(read_pixel(5, 8) == 0);
+    CHECK(read_pixel(12, 11) == 0);
+    CHECK(read_pixel(8, 12) == 0);
+
+    CHECK(pop_clip() == OK);
+    CHECK(pop_clip() == OK);
+    CHECK(pop_clip() == CLIP_STACK_EMPTY);
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(count_color(COLOR_WHITE) == TEST_W * TEST_H);
+}
+
+#define UNTOUCHED 0xDEADBEEF
+
+// Mark every front-buffer pixel so a present's writes can be counted.
+static void poison_front(void) {
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++) test_fb[i] = UNTOUCHED;
+}
+
+// With dirty tracking on, one drawn pixel is all swap_dirty() copies, and
+// a second swap with nothing drawn copies nothing.
+static void test_swap_dirty_one_pixel(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    enable_dirty_tracking(1);
+    clear_dirty();
+    poison_front();
+
+    draw_pixel(5, 9, COLOR_WHITE);
+    CHECK(swap_dirty() == OK);
+    CHECK(count_color(UNTOUCHED) == TEST_W * TEST_H - 1);
+    CHECK(test_fb[9 * TEST_W + 5] == COLOR_WHITE);
+
+    poison_front();
+    CHECK(swap_dirty() == OK);
+    CHECK(count_color(UNTOUCHED) == TEST_W * TEST_H);
+    enable_dirty_tracking(0);
+}
+
+// Each pixel of a labelled block has its own colour, so moved content can be
+// traced back to where it came from.
+static uint32_t label(uint32_t col, uint32_t row) {
+    return color((uint8_t)(16 * row + col + 1), 0, 0);
+}
+
+static void draw_labelled_block(uint32_t x, uint32_t y, uint32_t w, uint32_t h) {
+    for (uint32_t row = 0; row < h; row++)
+        for (uint32_t col = 0; col < w; col++)
+            draw_pixel(x + col, y + row, label(col, row));
+}
+
+// Scrolling a 4x4 block up one row moves rows 1-3 to rows 0-2 and fills the
+// bottom row; nothing outside the block changes.
+static void test_scroll_up(void) {
+    reset();
+    draw_labelled_block(4, 4, 4, 4);
+    CHECK(scroll_rect(4, 4, 4, 4, 0, -1, COLOR_BLUE) == OK);
+    for (uint32_t col = 0; col < 4; col++) {
+        for (uint32_t row = 0; row < 3; row++)
+            CHECK(read_pixel(4 + col, 4 + row) == label(col, row + 1));
+        CHECK(read_pixel(4 + col, 7) == COLOR_BLUE);
+        CHECK(read_pixel(4 + col, 3) == 0);
+        CHECK(read_pixel(4 + col, 8) == 0);
+    }
+}
+
+// Reading back a drawn block gives its pixels row by row; a region hanging
+// off the screen is clipped and reports its clipped size.
+static void test_read_region(void) {
+    reset();
+    draw_labelled_block(3, 5, 4, 3);
+    uint32_t out[12], w = 0, h = 0;
+    CHECK(read_region(3, 5, 4, 3, out, 12, &w, &h) == OK);
+    CHECK(w == 4 && h == 3);
+    uint32_t wrong = 0;
+    for (uint32_t row = 0; row < 3; row++)
+        for (uint32_t col = 0; col < 4; col++)
+            if (out[row * 4 + col] != label(col, row)) wrong++;
+    CHECK(wrong == 0);
+
+    CHECK(read_region(3, 5, 4, 3, out, 11, &w, &h) == BUFFER_TOO_SMALL);
+    CHECK(read_region(TEST_W - 2, 0, 4, 3, out, 12, &w, &h) == OK);
+    CHECK(w == 2 && h == 3);
+}
+
+// A rectangle running off the right edge is cut at the edge: the visible
+// part is filled and nothing wraps onto the next row.
+static void test_rect_off_right_edge(void) {
+    reset();
+    CHECK(draw_rect(TEST_W - 3, 4, 10, 2, COLOR_WHITE) == OK);
+    CHECK(count_color(COLOR_WHITE) == 3 * 2);
+    CHECK(read_pixel(TEST_W - 1, 5) == COLOR_WHITE);
+    CHECK(read_pixel(0, 5) == 0);
+    CHECK(read_pixel(0, 6) == 0);
+    CHECK(draw_rect(TEST_W, 0, 4, 4, COLOR_WHITE) == FAILED_TO_DRAW);
+    CHECK(draw_rect(TEST_W - 1, TEST_H - 1, UINT32_MAX, UINT32_MAX, COLOR_RED) == OK);
+    CHECK(count_color(COLOR_RED) == 1);
+}
+
+// The row-filling draw_rect() matches a pixel-by-pixel fill exactly, over
+// odd and even widths and offsets.
+static void test_rect_matches_pixels(void) {
+    static uint32_t expected[TEST_W * TEST_H];
+    static const rect_t rects[] = {
+        { 0, 0, TEST_W, TEST_H }, { 1, 2, 29, 27 }, { 3, 1, 1, 30 }, { 2, 5, 16, 1 },
+    };
+    for (uint32_t i = 0; i < sizeof(rects) / sizeof(rects[0]); i++) {
+        rect_t r = rects[i];
+        reset();
+        for (uint32_t y = r.y; y < r.y + r.h; y++)
+            for (uint32_t x = r.x; x < r.x + r.w; x++)
+                draw_pixel(x, y, COLOR_CORAL);
+        memcpy(expected, test_fb, sizeof(expected));
+
+        reset();
+        CHECK(draw_rect(r.x, r.y, r.w, r.h, COLOR_CORAL) == OK);
+        CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+    }
+}
+
+// clear_all() wipes the visible framebuffer along with the back buffer,
+// ignoring any clip.
+static void test_clear_all(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(swap_buffers() == OK);
+    CHECK(count_color(COLOR_WHITE) == TEST_W * TEST_H);
+
+    CHECK(push_clip(0, 0, 4, 4) == OK);
+    CHECK(clear_all(0) == OK);
+    CHECK(pop_clip() == OK);
+    CHECK(count_color(0) == TEST_W * TEST_H);
+    uint32_t *back = display_back_buffer(), left = 0;
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++)
+        if (back[i]) left++;
+    CHECK(left == 0);
+}
+
+// A rejected mode leaves the current one in place: drawing still lands in
+// the buffer that was set up before.
+static void test_init_rejects_bad_mode(void) {
+    static uint32_t other[8 * 8];
+    reset();
+    CHECK(init_display_with(0, 8, 8, 32) == NULL_POINTER);
+    CHECK(init_display_with(other, 0, 8, 32) == INVALID_DISPLAY_PARAMS);
+    CHECK(init_display_with(other, 9, 8, 32) == INVALID_DISPLAY_PARAMS);
+    CHECK(init_display_format(other, 8, 8, 32, (pixel_format)99) == INVALID_DISPLAY_PARAMS);
+    CHECK(display_width() == TEST_W && display_buffer() == test_fb);
+
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(count_color(COLOR_WHITE) == TEST_W * TEST_H);
+    CHECK(other[0] == 0);
+}
+
+// Colours are packed to RGB565 by dropping the low bits of each channel, and
+// a 16bpp framebuffer receives those words when the back buffer is swapped.
+static void test_rgb565(void) {
+    CHECK(pack_rgb565(0xFF123456) == 0x11AA);
+    CHECK(pack_rgb565(COLOR_WHITE) == 0xFFFF);
+    CHECK(pack_rgb565(color(255, 0, 0)) == 0xF800);
+    CHECK(pack_rgb565(color(0, 255, 0)) == 0x07E0);
+    CHECK(pack_rgb565(color(0, 0, 255)) == 0x001F);
+    CHECK(convert_pixel(0xFF123456, PIXEL_RGB565) == 0x11AA);
+
+    static uint32_t fb[4];
+    uint16_t words[8];
+    CHECK(init_display_format(fb, 4, 2, 8, PIXEL_RGB565) == OK);
+    draw_rect(0, 0, 4, 2, 0xFF123456);
+    draw_pixel(3, 1, COLOR_WHITE);
+    CHECK(swap_buffers() == OK);
+    memcpy(words, fb, sizeof(words));
+    CHECK(words[0] == 0x11AA && words[6] == 0x11AA);
+    CHECK(words[7] == 0xFFFF);
+    CHECK(read_pixel(3, 1) == COLOR_WHITE);
+}
+
+// With a BGR channel layout, red is packed into the low byte of each
+// framebuffer word while the back buffer keeps canonical 0xRRGGBB.
+static void test_bgr_layout(void) {
+    static const channel_layout_t bgr = { 0, 8, 8, 8, 16, 8 };
+    pixel_format format = PIXEL_XRGB8888;
+    CHECK(pixel_format_from_layout(32, &bgr, &format) == OK);
+    CHECK(format == PIXEL_XBGR8888);
+    CHECK(convert_pixel(color(0xAA, 0xBB, 0xCC), PIXEL_XBGR8888) == 0xCCBBAA);
+
+    reset();
+    CHECK(set_channel_layout(&bgr) == OK);
+    CHECK(pack_color(255, 0, 0) == 0x0000FF);
+    draw_pixel(0, 0, color(255, 0, 0));
+    draw_pixel(1, 0, color(0, 0, 255));
+    CHECK(swap_buffers() == OK);
+    CHECK((test_fb[0] & 0xFFFFFF) == 0x0000FF);
+    CHECK((test_fb[1] & 0xFFFFFF) == 0xFF0000);
+    CHECK(read_pixel(0, 0) == color(255, 0, 0));
+}
+
+// memset32() matches a plain loop for odd and even lengths and for starts
+// on either half of a 64-bit word, and writes nothing past the end.
+static void test_memset32(void) {
+    uint32_t batched[24], naive[24];
+    for (uint32_t start = 0; start < 2; start++) {
+        for (uint32_t len = 0; len <= 9; len++) {
+            for (uint32_t i = 0; i < 24; i++) batched[i] = naive[i] = UNTOUCHED;
+            memset32(batched + start, 0xFF123456, len);
+            for (uint32_t i = 0; i < len; i++) naive[start + i] = 0xFF123456;
+            CHECK(memcmp(batched, naive, sizeof(batched)) == 0);
+        }
+    }
+}
+
+// try_read_pixel() tells an off-screen coordinate from a black pixel, and
+// leaves the output alone when it refuses.
+static void test_try_read_pixel(void) {
+    reset();
+    draw_pixel(TEST_W - 1, 0, COLOR_RED);
+    uint32_t out = UNTOUCHED;
+    CHECK(try_read_pixel(TEST_W, 0, &out) == OUT_OF_BOUNDS);
+    CHECK(try_read_pixel(0, TEST_H, &out) == OUT_OF_BOUNDS);
+    CHECK(out == UNTOUCHED);
+    CHECK(try_read_pixel(TEST_W - 1, 0, &out) == OK);
+    CHECK(out == COLOR_RED);
+    CHECK(try_read_pixel(0, 0, &out) == OK);
+    CHECK(out == 0);
+    CHECK(try_read_pixel(0, 0, 0) == NULL_POINTER);
+}
+
+// A bitmap straddling the right edge draws its left columns from the right
+// source pixels and drops the rest instead of wrapping them onto row start.
+static void test_bitmap_off_right_edge(void) {
+    uint32_t bitmap[4 * 2];
+    for (uint32_t row = 0; row < 2; row++)
+        for (uint32_t col = 0; col < 4; col++)
+            bitmap[row * 4 + col] = label(col, row);
+    reset();
+    CHECK(draw_bitmap(TEST_W - 2, 3, 4, 2, bitmap) == OK);
+    for (uint32_t row = 0; row < 2; row++) {
+        CHECK(read_pixel(TEST_W - 2, 3 + row) == label(0, row));
+        CHECK(read_pixel(TEST_W - 1, 3 + row) == label(1, row));
+        CHECK(read_pixel(0, 4 + row) == 0);
+        CHECK(read_pixel(1, 4 + row) == 0);
+    }
+    CHECK(count_color(0) == TEST_W * TEST_H - 2 * 2);
+
+    reset();
+    draw_bitmap(UINT32_MAX - 1, 0, 4, 2, bitmap);
+    CHECK(count_color(0) == TEST_W * TEST_H);
+}
+
+static int rect_equals(rect_t r, uint32_t x, uint32_t y, uint32_t w, uint32_t h) {
+    return r.x == x && r.y == y && r.w == w && r.h == h;
+}
+
+// Overlapping, disjoint, touching and nested rectangles, including one that
+// reaches past UINT32_MAX.
+static void test_rect_ops(void) {
+    rect_t a = { 2, 3, 10, 6 }, out = { 7, 7, 7, 7 };
+    CHECK(rect_intersect(a, (rect_t){ 8, 5, 10, 10 }, &out));
+    CHECK(rect_equals(out, 8, 5, 4, 4));
+
+    out = (rect_t){ 7, 7, 7, 7 };
+    CHECK(!rect_intersect(a, (rect_t){ 20, 20, 4, 4 }, &out));
+    CHECK(!rect_intersect(a, (rect_t){ 12, 3, 4, 4 }, &out));
+    CHECK(rect_equals(out, 7, 7, 7, 7));
+
+    CHECK(rect_intersect(a, (rect_t){ 4, 4, 2, 2 }, &out));
+    CHECK(rect_equals(out, 4, 4, 2, 2));
+    CHECK(rect_intersect((rect_t){ 5, 5, UINT32_MAX, UINT32_MAX }, a, &out));
+    CHECK(rect_equals(out, 5, 5, 7, 4));
+
+    CHECK(rect_contains(a, 2, 3) && rect_contains(a, 11, 8));
+    CHECK(!rect_contains(a, 12, 8) && !rect_contains(a, 1, 3));
+    CHECK(rect_is_empty((rect_t){ 1, 1, 0, 5 }));
+    CHECK(rect_equals(rect_union(a, (rect_t){ 20, 0, 1, 1 }), 2, 0, 19, 9));
+    CHECK(rect_equals(rect_union(a, (rect_t){ 0, 0, 0, 0 }), 2, 3, 10, 6));
+}
+
+// At scale 2 drawing is in 16x16 logical pixels, and each one is presented
+// as a 2x2 block of screen pixels.
+static void test_scale_two(void) {
+    reset();
+    CHECK(set_scale(0) == INVALID_DISPLAY_PARAMS);
+    CHECK(set_scale(2) == OK);
+    draw_pixel(3, 5, COLOR_WHITE);
+    draw_pixel(TEST_W / 2 - 1, TEST_H / 2 - 1, COLOR_RED);
+    draw_pixel(TEST_W / 2, 0, COLOR_RED);
+    CHECK(swap_buffers() == OK);
+
+    CHECK(count_color(COLOR_WHITE) == 4);
+    CHECK(test_fb[10 * TEST_W + 6] == COLOR_WHITE);
+    CHECK(test_fb[10 * TEST_W + 7] == COLOR_WHITE);
+    CHECK(test_fb[11 * TEST_W + 6] == COLOR_WHITE);
+    CHECK(test_fb[11 * TEST_W + 7] == COLOR_WHITE);
+    CHECK(count_color(COLOR_RED) == 4);
+    CHECK(test_fb[TEST_W * TEST_H - 1] == COLOR_RED);
+}
+
+// Pixels written straight into the back buffer at y * stride + x read back
+// through read_pixel() and are presented by the next swap.
+static void test_back_buffer_writes(void) {
+    reset();
+    CHECK(display_back_buffer() == 0);
+    CHECK(init_back_buffer() == OK);
+    uint32_t *back = display_back_buffer(), stride = display_stride();
+    CHECK(back != 0 && stride == TEST_W);
+    if (!back) return;
+
+    back[7 * stride + 3] = COLOR_RED;
+    back[(TEST_H - 1) * stride + TEST_W - 1] = COLOR_BLUE;
+    CHECK(read_pixel(3, 7) == COLOR_RED);
+    CHECK(read_pixel(TEST_W - 1, TEST_H - 1) == COLOR_BLUE);
+    CHECK(test_fb[7 * TEST_W + 3] == 0);
+    CHECK(swap_buffers() == OK);
+    CHECK(test_fb[7 * TEST_W + 3] == COLOR_RED);
+}
+
+static uint64_t mock_ticks, mock_step;
+
+static uint64_t mock_now(void) {
+    return mock_ticks += mock_step;
+}
+
+// With a clock that advances a fixed step per call, every swap counts a
+// frame and the interval is that step; a change of pace shows up on the
+// next swap, and without a clock frames are still counted.
+static void test_frame_timing(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    mock_ticks = 1000;
+    mock_step  = 16;
+    set_time_source(mock_now);
+    uint64_t frames = frame_stats().frames;
+
+    for (int i = 0; i < 4; i++) CHECK(swap_buffers() == OK);
+    frame_stats_t s = frame_stats();
+    CHECK(s.frames == frames + 4);
+    CHECK(s.frame_ticks == 16);
+    CHECK(s.last_swap_ticks == 1000 + 4 * 16);
+
+    mock_step = 40;
+    CHECK(swap_buffers() == OK);
+    CHECK(frame_stats().frame_ticks == 40);
+    CHECK(frame_stats().last_swap_ticks == mock_ticks);
+
+    set_time_source(0);
+    CHECK(swap_buffers() == OK);
+    CHECK(frame_stats().frames == frames + 6);
+    CHECK(frame_stats().last_swap_ticks == 1000 + 4 * 16 + 40);
+}
+
+// Every error has its own readable message, so a caller can report why
+// init_display() failed; only values outside the enum fall back to
+// "unknown error".
+static void test_error_strings(void) {
+    const char *unknown = fb_error_str((fb_error)-1);
+    for (int a = OK; a <= MALFORMED_DATA; a++) {
+        CHECK(strcmp(fb_error_str((fb_error)a), unknown) != 0);
+        for (int b = OK; b < a; b++)
+            CHECK(strcmp(fb_error_str((fb_error)a), fb_error_str((fb_error)b)) != 0);
+    }
+    CHECK(strcmp(fb_error_str(NO_FRAMEBUFFER), fb_error_str(NULL_POINTER)) != 0);
+}
+
+static uint32_t vsync_calls;
+
+static void count_vsync(void) {
+    vsync_calls++;
+}
+
+// The vsync hook runs exactly once for every full-frame present and not at
+// all once it is cleared.
+static void test_vsync_once_per_swap(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    vsync_calls = 0;
+    set_vsync_wait(count_vsync);
+    for (int i = 0; i < 3; i++) CHECK(swap_buffers() == OK);
+    CHECK(vsync_calls == 3);
+    CHECK(present_diff(0) == OK);
+    CHECK(vsync_calls == 4);
+
+    set_vsync_wait(0);
+    CHECK(swap_buffers() == OK);
+    CHECK(vsync_calls == 4);
+}
+
+// Two far-apart pixels are presented as two 1x1 copies rather than their
+// bounding box; more separate areas than are tracked present the whole screen.
+static void test_swap_dirty_disjoint(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    enable_dirty_tracking(1);
+    clear_dirty();
+    poison_front();
+
+    draw_pixel(2, 3, COLOR_WHITE);
+    draw_pixel(28, 29, COLOR_WHITE);
+    CHECK(swap_dirty() == OK);
+    CHECK(count_color(UNTOUCHED) == TEST_W * TEST_H - 2);
+    CHECK(test_fb[3 * TEST_W + 2] == COLOR_WHITE);
+    CHECK(test_fb[29 * TEST_W + 28] == COLOR_WHITE);
+
+    poison_front();
+    for (uint32_t i = 0; i < 17; i++) draw_pixel(i * 2 % TEST_W, i * 2 / TEST_W * 2, COLOR_RED);
+    CHECK(swap_dirty() == OK);
+    CHECK(count_color(UNTOUCHED) == 0);
+    enable_dirty_tracking(0);
+}
+
+// A canonical colour converted to each layout puts every channel in that
+// layout's byte, and an RGBX framebuffer receives those bytes on a swap.
+static void test_convert_layouts(void) {
+    uint32_t c = color(0x11, 0x22, 0x33);
+    CHECK(convert_pixel(c, PIXEL_XRGB8888) == 0x112233);
+    CHECK(convert_pixel(c, PIXEL_RGBX8888) == 0x11223300);
+    CHECK(convert_pixel(c, PIXEL_XBGR8888) == 0x332211);
+    CHECK(convert_pixel(c, PIXEL_RGB565) == 0x1106);
+
+    static uint32_t fb[2 * 2];
+    CHECK(init_display_format(fb, 2, 2, 8, PIXEL_RGBX8888) == OK);
+    CHECK(display_format() == PIXEL_RGBX8888);
+    draw_pixel(1, 0, c);
+    CHECK(swap_buffers() == OK);
+    uint8_t bytes[4];
+    memcpy(bytes, &fb[1], sizeof(bytes));
+    CHECK(bytes[0] == 0x00 && bytes[1] == 0x33 && bytes[2] == 0x22 && bytes[3] == 0x11);
+    CHECK(read_pixel(1, 0) == c);
+}
+
+void display_tests(void) {
+    test_pitch_wider_than_width();
+    test_present_diff();
+    test_display_index();
+    test_with_display();
+    test_clip_stack();
+    test_swap_dirty_one_pixel();
+    test_scroll_up();
+    test_read_region();
+    test_rect_off_right_edge();
+    test_rect_matches_pixels();
+    test_clear_all();
+    test_init_rejects_bad_mode();
+    test_rgb565();
+    test_bgr_layout();
+    test_memset32();
+    test_try_read_pixel();
+    test_bitmap_off_right_edge();
+    test_rect_ops();
+    test_scale_two();
+    test_back_buffer_writes();
+    test_frame_timing();
+    test_error_strings();
+    test_vsync_once_per_swap();
+    test_swap_dirty_disjoint();
+    test_convert_layouts();
+}
@@ -0,0 +1,61 @@
+#include "check.h"
+#include <string.h>
+
+// A PSF2 font of three 8x4 glyphs: a blank one, a one-pixel bar in column 3
+// and a five-pixel bar in columns 1..5. Each glyph is four one-byte rows, so
+// it packs into one little-endian word.
+static const uint32_t psf2_blob[] = {
+    0x864AB572, 0, 32, 0, 3, 4, 4, 8,
+    0x00000000, 0x10101010, 0x7C7C7C7C,
+};
+
+static void test_psf2_metrics(void) {
+    font_t font;
+    const uint8_t *data = (const uint8_t *)psf2_blob;
+    CHECK(font_from_psf2(&font, data, sizeof(psf2_blob)) == FONT_OK);
+    CHECK(font.num_glyphs == 3);
+    CHECK(font.height == 4);
+
+    CHECK(font.glyphs[0].width == 0);
+    CHECK(font.glyphs[0].advance == 4);
+    CHECK(font.glyphs[1].width == 1);
+    CHECK(font.glyphs[1].height == 4);
+    CHECK(font.glyphs[1].bearing_x == -3);
+    CHECK(font.glyphs[1].advance == 2);
+    CHECK(font.glyphs[2].width == 5);
+    CHECK(font.glyphs[2].bearing_x == -1);
+    CHECK(font.glyphs[2].advance == 6);
+
+    // Each glyph's ink starts at the pen, so the bars sit side by side with
+    // one pixel between them.
+    reset();
+    CHECK(draw_text_font(0, 0, "\x01\x02", &font, COLOR_WHITE) == 8);
+    CHECK(read_pixel(0, 3) == COLOR_WHITE);
+    CHECK(read_pixel(1, 3) == 0);
+    CHECK(read_pixel(2, 0) == COLOR_WHITE);
+    CHECK(read_pixel(6, 0) == COLOR_WHITE);
+    CHECK(read_pixel(7, 0) == 0);
+    font_free(&font);
+}
+
+static void test_psf2_errors(void) {
+    font_t font;
+    const uint8_t *data = (const uint8_t *)psf2_blob;
+    CHECK(font_from_psf2(0, data, sizeof(psf2_blob)) == FONT_NULL_POINTER);
+    CHECK(font_from_psf2(&font, 0, sizeof(psf2_blob)) == FONT_NULL_POINTER);
+    CHECK(font_from_psf2(&font, data, 16) == FONT_TRUNCATED);
+    CHECK(font_from_psf2(&font, data, sizeof(psf2_blob) - 1) == FONT_TRUNCATED);
+
+    uint32_t bad[sizeof(psf2_blob) / 4];
+    memcpy(bad, psf2_blob, sizeof(bad));
+    bad[0] = 0x12345678;
+    CHECK(font_from_psf2(&font, (const uint8_t *)bad, sizeof(bad)) == FONT_BAD_MAGIC);
+    bad[0] = psf2_blob[0];
+    bad[5] = 5;
+    CHECK(font_from_psf2(&font, (const uint8_t *)bad, sizeof(bad)) == FONT_BAD_HEADER);
+}
+
+void font_tests(void) {
+    test_psf2_metrics();
+    test_psf2_errors();
+}
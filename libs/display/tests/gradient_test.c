@@ -0,0 +1,96 @@
+#include "check.h"
+
+// Sixteen rows put row r at stop position 17 * r, so row 7 lands exactly on
+// a middle stop at 119 and the end rows on the end stops.
+static void test_three_stops(void) {
+    reset();
+    const gradient_stop_t stops[] = {
+        { 0, COLOR_RED }, { 119, COLOR_YELLOW }, { 255, COLOR_BLUE },
+    };
+    CHECK(draw_gradient_stops_vertical(0, 0, 4, 16, stops, 3) == OK);
+    CHECK(read_pixel(0, 0) == COLOR_RED);
+    CHECK(read_pixel(3, 7) == COLOR_YELLOW);
+    CHECK(read_pixel(0, 15) == COLOR_BLUE);
+    CHECK(read_pixel(0, 16) == 0);
+
+    // Halfway between the first two stops the green channel is half up.
+    CHECK(read_pixel(0, 3) == rgba(0xFF, 0x6D, 0x00, 0xFF));
+}
+
+// The two-colour gradients run over the same row / (height - 1) fraction,
+// so they end on their second colour too.
+static void test_two_colours(void) {
+    reset();
+    CHECK(draw_gradient_vertical(0, 0, 2, 3, COLOR_BLACK, COLOR_WHITE) == OK);
+    CHECK(read_pixel(0, 0) == COLOR_BLACK);
+    CHECK(read_pixel(0, 1) == color(0x7F, 0x7F, 0x7F));
+    CHECK(read_pixel(0, 2) == COLOR_WHITE);
+
+    CHECK(draw_gradient_horizontal_srgb(0, 4, 5, 1, COLOR_RED, COLOR_BLUE) == OK);
+    CHECK(read_pixel(0, 4) == COLOR_RED);
+    CHECK(read_pixel(4, 4) == COLOR_BLUE);
+}
+
+// Four rows sit at 0, 1/3, 2/3 and 1 of the way down, and each row is one
+// colour across its width. Clipping away the top rows leaves the colours of
+// the rows that remain unchanged.
+static void test_four_rows(void) {
+    const uint32_t rows[4] = {
+        color(0x00, 0x00, 0xFF), color(0x33, 0x55, 0xAA),
+        color(0x66, 0xAA, 0x55), color(0x99, 0xFF, 0x00),
+    };
+    reset();
+    CHECK(draw_gradient_vertical(2, 1, 3, 4, rows[0], rows[3]) == OK);
+    for (uint32_t row = 0; row < 4; row++)
+        for (uint32_t col = 0; col < 3; col++)
+            CHECK(read_pixel(2 + col, 1 + row) == rows[row]);
+    CHECK(read_pixel(2, 5) == 0);
+
+    reset();
+    CHECK(push_clip(0, 3, TEST_W, TEST_H - 3) == OK);
+    CHECK(draw_gradient_vertical(2, 1, 3, 4, rows[0], rows[3]) == OK);
+    CHECK(pop_clip() == OK);
+    CHECK(read_pixel(2, 2) == 0);
+    CHECK(read_pixel(2, 3) == rows[2]);
+    CHECK(read_pixel(2, 4) == rows[3]);
+}
+
+// Mixing in linear light puts the middle of a black-to-white gradient at a
+// lighter grey than mixing the stored values does; the ends are the same.
+static void test_srgb_midpoint(void) {
+    reset();
+    CHECK(draw_gradient_horizontal(0, 0, 3, 1, COLOR_BLACK, COLOR_WHITE) == OK);
+    CHECK(draw_gradient_horizontal_srgb(0, 1, 3, 1, COLOR_BLACK, COLOR_WHITE) == OK);
+    uint32_t plain = read_pixel(1, 0), srgb = read_pixel(1, 1);
+    CHECK(red_of(srgb) > red_of(plain) + 32);
+    CHECK(red_of(srgb) == green_of(srgb) && green_of(srgb) == blue_of(srgb));
+    CHECK(read_pixel(0, 1) == COLOR_BLACK);
+    CHECK(read_pixel(2, 1) == COLOR_WHITE);
+}
+
+// A gradient circle is center_color in the middle and close to edge_color at
+// the rim, covers the same pixels as draw_circle() and gets darker steadily
+// towards a black edge.
+static void test_circle_gradient(void) {
+    reset();
+    CHECK(draw_circle(16, 16, 10, COLOR_RED) == OK);
+    uint32_t covered = TEST_W * TEST_H - count_color(0);
+
+    reset();
+    CHECK(fill_circle_gradient(16, 16, 10, COLOR_WHITE, COLOR_BLACK) == OK);
+    CHECK(read_pixel(16, 16) == COLOR_WHITE);
+    CHECK(red_of(read_pixel(26, 16)) <= 8);
+    CHECK(red_of(read_pixel(16, 6)) <= 8);
+    CHECK(TEST_W * TEST_H - count_color(0) == covered);
+    CHECK(read_pixel(27, 16) == 0);
+    for (uint32_t x = 17; x <= 26; x++)
+        CHECK(red_of(read_pixel(x, 16)) < red_of(read_pixel(x - 1, 16)));
+}
+
+void gradient_tests(void) {
+    test_three_stops();
+    test_two_colours();
+    test_four_rows();
+    test_srgb_midpoint();
+    test_circle_gradient();
+}
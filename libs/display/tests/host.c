@@ -0,0 +1,19 @@
+#include <stdio.h>
+
+// itoa() lives in the userland stdio.c next to a printf that makes kernel
+// syscalls, so the host build, which uses the host printf, carries a copy.
+void itoa(int val, char *buf) {
+    unsigned int v = val < 0 ? 0u - (unsigned int)val : (unsigned int)val;
+    int i = 0;
+    do {
+        buf[i++] = (char)('0' + v % 10);
+        v /= 10;
+    } while (v);
+    if (val < 0) buf[i++] = '-';
+    buf[i] = '\0';
+    for (int j = 0, k = i - 1; j < k; j++, k--) {
+        char tmp = buf[j];
+        buf[j] = buf[k];
+        buf[k] = tmp;
+    }
+}
@@ -0,0 +1,112 @@
+#include "check.h"
+#include <string.h>
+
+// A 2x2 image in two runs: two opaque red pixels, then two half-transparent
+// white ones that blend over the black screen.
+static const uint8_t rle_2x2[] = {
+    0x02, 0x00, 0x00, 0x00, 0xFF, 0xFF,
+    0x02, 0x00, 0xFF, 0xFF, 0xFF, 0x80,
+};
+
+static void test_rle(void) {
+    reset();
+    CHECK(draw_rle(3, 4, 2, 2, rle_2x2, sizeof(rle_2x2)) == OK);
+    CHECK(read_pixel(3, 4) == COLOR_RED);
+    CHECK(read_pixel(4, 4) == COLOR_RED);
+    CHECK(read_pixel(3, 5) == 0xFF808080);
+    CHECK(read_pixel(4, 5) == 0xFF808080);
+    CHECK(read_pixel(5, 4) == 0);
+
+    CHECK(draw_rle(0, 0, 2, 2, rle_2x2, sizeof(rle_2x2) - 1) == MALFORMED_DATA);
+    CHECK(draw_rle(0, 0, 2, 3, rle_2x2, sizeof(rle_2x2)) == MALFORMED_DATA);
+    CHECK(draw_rle(0, 0, 2, 2, 0, 0) == NULL_POINTER);
+    CHECK(read_pixel(0, 0) == 0);
+}
+
+// A 3x2 QOI image using each chunk type: an RGB literal, a run, an RGBA
+// literal, a diff, an index back to the first colour and a luma.
+static const uint8_t qoi_3x2[] = {
+    'q', 'o', 'i', 'f', 0, 0, 0, 3, 0, 0, 0, 2, 4, 0,
+    0xFE, 0xFF, 0x00, 0x00,
+    0xC0,
+    0xFF, 0x00, 0x00, 0xFF, 0xFF,
+    0x79,
+    0x32,
+    0xA2, 0x96,
+    0, 0, 0, 0, 0, 0, 0, 1,
+};
+
+static void test_qoi(void) {
+    static const uint32_t expected[] = {
+        0xFFFF0000, 0xFFFF0000, 0xFF0000FF,
+        0xFF0100FE, 0xFFFF0000, 0xFF020200,
+    };
+    image_t img;
+    CHECK(qoi_decode(&img, qoi_3x2, sizeof(qoi_3x2)) == IMAGE_OK);
+    CHECK(img.width == 3 && img.height == 2);
+    for (uint32_t i = 0; i < 6; i++)
+        CHECK(img.pixels[i] == expected[i]);
+    image_free(&img);
+
+    reset();
+    CHECK(draw_qoi(1, 1, qoi_3x2, sizeof(qoi_3x2)) == IMAGE_OK);
+    CHECK(read_pixel(1, 1) == expected[0]);
+    CHECK(read_pixel(3, 2) == expected[5]);
+    CHECK(draw_qoi(TEST_W, 0, qoi_3x2, sizeof(qoi_3x2)) == IMAGE_DRAW_FAILED);
+    CHECK(draw_qoi(0, 0, qoi_3x2, sizeof(qoi_3x2) - 1) == IMAGE_TRUNCATED);
+}
+
+// A bottom-up 2x2 24-bit BMP: the first row in the file is the bottom one,
+// pixels are stored blue first, and each 6-byte row is padded to 8.
+static const uint8_t bmp_2x2[] = {
+    'B', 'M', 70, 0, 0, 0, 0, 0, 0, 0, 54, 0, 0, 0,
+    40, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 24, 0,
+    0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0x00, 0x00, 0xFF, 0x00, 0xFF, 0x00, 0, 0,
+    0xFF, 0x00, 0x00, 0x10, 0x20, 0x30, 0, 0,
+};
+
+static void test_bmp(void) {
+    image_t img;
+    CHECK(bmp_parse(&img, bmp_2x2, sizeof(bmp_2x2)) == IMAGE_OK);
+    CHECK(img.width == 2 && img.height == 2);
+    CHECK(img.pixels[0] == color(0x00, 0x00, 0xFF));
+    CHECK(img.pixels[1] == color(0x30, 0x20, 0x10));
+    CHECK(img.pixels[2] == color(0xFF, 0x00, 0x00));
+    CHECK(img.pixels[3] == color(0x00, 0xFF, 0x00));
+    image_free(&img);
+
+    CHECK(bmp_parse(&img, bmp_2x2, sizeof(bmp_2x2) - 1) == IMAGE_TRUNCATED);
+    uint8_t bad[sizeof(bmp_2x2)];
+    memcpy(bad, bmp_2x2, sizeof(bad));
+    bad[0] = 'X';
+    CHECK(bmp_parse(&img, bad, sizeof(bad)) == IMAGE_BAD_MAGIC);
+}
+
+// Black and white columns alternate, so every 2x2 block of the 4x4 source is
+// half white and averages, rounded, to a mid grey. One lighter block shows
+// the blocks are averaged separately.
+static void test_downscale(void) {
+    uint32_t pixels[4 * 4];
+    for (uint32_t i = 0; i < 4 * 4; i++)
+        pixels[i] = i % 2 ? COLOR_WHITE : COLOR_BLACK;
+    pixels[3 * 4 + 2] = COLOR_WHITE;
+    image_t src = { 4, 4, pixels }, dst;
+
+    CHECK(image_downscale(&dst, &src, 2, 2) == IMAGE_OK);
+    CHECK(dst.width == 2 && dst.height == 2);
+    CHECK(dst.pixels[0] == color(0x80, 0x80, 0x80));
+    CHECK(dst.pixels[1] == color(0x80, 0x80, 0x80));
+    CHECK(dst.pixels[2] == color(0x80, 0x80, 0x80));
+    CHECK(dst.pixels[3] == color(0xBF, 0xBF, 0xBF));
+    image_free(&dst);
+
+    CHECK(image_downscale(&dst, &src, 0, 2) == IMAGE_BAD_HEADER);
+}
+
+void image_tests(void) {
+    test_rle();
+    test_qoi();
+    test_bmp();
+    test_downscale();
+}
@@ -0,0 +1,61 @@
+#include "check.h"
+
+// A sprite drawn into a layer keeps its colour when composited. Drawing
+// stores opaque pixels, so the half-transparent one is flattened onto the
+// layer's black; pixels nothing was drawn to stay transparent.
+static void test_sprite_into_layer(void) {
+    static const uint32_t sprite[3] = { 0xFF204060, 0x80FFFFFF, 0x00FF0000 };
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_BLUE);
+
+    layer_t *layer = layer_new(4, 1);
+    CHECK(layer != 0);
+    if (!layer) return;
+    CHECK(bind_canvas(layer->canvas) == OK);
+    draw_sprite(0, 0, 3, 1, sprite);
+    CHECK(bind_canvas(0) == OK);
+
+    layer->x = 2;
+    layer->y = 5;
+    CHECK(composite_layer(layer) == OK);
+    CHECK(read_pixel(2, 5) == 0xFF204060);
+    CHECK(read_pixel(3, 5) == 0xFF808080);
+    CHECK(read_pixel(4, 5) == COLOR_BLUE);
+    CHECK(read_pixel(5, 5) == COLOR_BLUE);
+
+    layer->opacity = 0;
+    draw_rect(0, 5, TEST_W, 1, COLOR_BLUE);
+    CHECK(composite_layer(layer) == OK);
+    CHECK(read_pixel(2, 5) == COLOR_BLUE);
+    layer_free(layer);
+}
+
+// While a canvas is bound, drawing and reads go to its buffer and are
+// clipped to its size; the screen is left alone until it is bound again.
+static void test_bound_canvas(void) {
+    reset();
+    canvas_t *canvas = canvas_new(6, 4);
+    CHECK(canvas != 0);
+    if (!canvas) return;
+    CHECK(bind_canvas(canvas) == OK);
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    draw_pixel(5, 3, COLOR_RED);
+    CHECK(read_pixel(5, 3) == COLOR_RED);
+    CHECK(bind_canvas(0) == OK);
+
+    CHECK(count_color(0) == TEST_W * TEST_H);
+    uint32_t white = 0;
+    for (uint32_t i = 0; i < 6 * 4; i++)
+        if (canvas->buffer[i] == COLOR_WHITE) white++;
+    CHECK(white == 6 * 4 - 1);
+    CHECK(canvas->buffer[3 * 6 + 5] == COLOR_RED);
+
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_BLUE);
+    CHECK(count_color(COLOR_BLUE) == TEST_W * TEST_H);
+    canvas_free(canvas);
+}
+
+void layer_tests(void) {
+    test_sprite_into_layer();
+    test_bound_canvas();
+}
@@ -0,0 +1,35 @@
+#include "check.h"
+
+void display_tests(void);
+void color_tests(void);
+void layer_tests(void);
+void console_tests(void);
+void font_tests(void);
+void gradient_tests(void);
+void polygon_tests(void);
+void image_tests(void);
+void snapshot_tests(void);
+void sprite_tests(void);
+void blur_tests(void);
+void shapes_tests(void);
+void text_tests(void);
+
+static void (*const suites[])(void) = {
+    display_tests,
+    color_tests,
+    layer_tests,
+    console_tests,
+    font_tests,
+    gradient_tests,
+    polygon_tests,
+    image_tests,
+    snapshot_tests,
+    sprite_tests,
+    blur_tests,
+    shapes_tests,
+    text_tests,
+};
+
+int main(void) {
+    return run_suites("display", suites, sizeof(suites) / sizeof(suites[0]));
+}
@@ -0,0 +1,95 @@
+#include "check.h"
+#include <string.h>
+
+// Pixel centres are sampled, so a square from (2, 3) to (12, 9) covers
+// exactly its 10 x 6 area.
+static void test_square_area(void) {
+    reset();
+    const point_t square[] = { { 2, 3 }, { 12, 3 }, { 12, 9 }, { 2, 9 } };
+    CHECK(fill_polygon(square, 4, COLOR_WHITE) == OK);
+    CHECK(count_color(COLOR_WHITE) == 10 * 6);
+    CHECK(read_pixel(2, 3) == COLOR_WHITE);
+    CHECK(read_pixel(11, 8) == COLOR_WHITE);
+    CHECK(read_pixel(12, 8) == 0);
+    CHECK(read_pixel(11, 9) == 0);
+}
+
+// Even-odd: a square traced twice in opposite directions cancels itself,
+// and a bowtie fills both lobes but not the gap between them.
+static void test_even_odd(void) {
+    reset();
+    const point_t twice[] = {
+        { 0, 0 }, { 8, 0 }, { 8, 8 }, { 0, 8 },
+        { 0, 0 }, { 0, 8 }, { 8, 8 }, { 8, 0 },
+    };
+    CHECK(fill_polygon(twice, 8, COLOR_WHITE) == OK);
+    CHECK(count_color(COLOR_WHITE) == 0);
+
+    const point_t bowtie[] = { { 0, 0 }, { 20, 20 }, { 20, 0 }, { 0, 20 } };
+    CHECK(fill_polygon(bowtie, 4, COLOR_WHITE) == OK);
+    CHECK(read_pixel(2, 10) == COLOR_WHITE);
+    CHECK(read_pixel(18, 10) == COLOR_WHITE);
+    CHECK(read_pixel(10, 2) == 0);
+    CHECK(read_pixel(10, 18) == 0);
+}
+
+// A polygon spanning the whole int32 range is clipped to the screen without
+// walking the rows above it or overflowing its edge crossings.
+static void test_huge_polygon(void) {
+    reset();
+    const point_t huge[] = {
+        { INT32_MIN, INT32_MIN }, { INT32_MAX, INT32_MIN },
+        { INT32_MAX, INT32_MAX }, { INT32_MIN, INT32_MAX },
+    };
+    CHECK(fill_polygon(huge, 4, COLOR_WHITE) == OK);
+    CHECK(count_color(COLOR_WHITE) == TEST_W * TEST_H);
+
+    reset();
+    const point_t sliver[] = { { INT32_MIN, INT32_MIN }, { INT32_MAX, INT32_MAX }, { INT32_MIN, INT32_MAX } };
+    CHECK(fill_polygon(sliver, 3, COLOR_WHITE) == OK);
+    CHECK(read_pixel(0, 31) == COLOR_WHITE);
+    CHECK(read_pixel(31, 0) == 0);
+}
+
+static int point_at(point_t p, int32_t x, int32_t y) {
+    return p.x == x && p.y == y;
+}
+
+// A quarter turn maps the unit square's corners (x, y) to (-y, x) exactly,
+// a half turn to (-x, -y), and translating afterwards shifts the result.
+// Filling through a transform covers the same pixels as filling the mapped
+// corners.
+static void test_rotate_unit_square(void) {
+    static const point_t square[4] = { { 0, 0 }, { 1, 0 }, { 1, 1 }, { 0, 1 } };
+    transform_t r90 = transform_rotate(90), r180 = transform_rotate(180);
+    CHECK(point_at(transform_apply(&r90, square[0]), 0, 0));
+    CHECK(point_at(transform_apply(&r90, square[1]), 0, 1));
+    CHECK(point_at(transform_apply(&r90, square[2]), -1, 1));
+    CHECK(point_at(transform_apply(&r90, square[3]), -1, 0));
+    CHECK(point_at(transform_apply(&r180, square[2]), -1, -1));
+
+    transform_t move = transform_translate(10, 20);
+    transform_t both = transform_multiply(&move, &r90);
+    CHECK(point_at(transform_apply(&both, square[2]), 9, 21));
+
+    static const point_t big[4] = { { 0, 0 }, { 8, 0 }, { 8, 8 }, { 0, 8 } };
+    static uint32_t expected[TEST_W * TEST_H];
+    point_t mapped[4];
+    transform_t place = transform_translate(20, 4);
+    place = transform_multiply(&place, &r90);
+    for (int i = 0; i < 4; i++) mapped[i] = transform_apply(&place, big[i]);
+    reset();
+    CHECK(fill_polygon(mapped, 4, COLOR_WHITE) == OK);
+    memcpy(expected, test_fb, sizeof(expected));
+    reset();
+    CHECK(fill_polygon_transformed(big, 4, &place, COLOR_WHITE) == OK);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+    CHECK(read_pixel(15, 8) == COLOR_WHITE);
+}
+
+void polygon_tests(void) {
+    test_square_area();
+    test_even_odd();
+    test_huge_polygon();
+    test_rotate_unit_square();
+}
@@ -0,0 +1,343 @@
+#include "check.h"
+#include <string.h>
+
+static uint32_t saved[TEST_W * TEST_H];
+
+static void save_screen(void) {
+    memcpy(saved, test_fb, sizeof(saved));
+}
+
+static int screen_matches_saved(void) {
+    return memcmp(saved, test_fb, sizeof(saved)) == 0;
+}
+
+// Axis-aligned lines have no fractional coverage, so the anti-aliased line
+// sets exactly the solid pixels draw_line() does.
+static void test_aa_line_axis_aligned(void) {
+    reset();
+    draw_line(3, 5, 20, 5, COLOR_WHITE);
+    draw_line(7, 2, 7, 25, COLOR_WHITE);
+    save_screen();
+
+    reset();
+    CHECK(draw_line_aa(3, 5, 20, 5, COLOR_WHITE) == OK);
+    CHECK(draw_line_aa(7, 2, 7, 25, COLOR_WHITE) == OK);
+    CHECK(screen_matches_saved());
+}
+
+// Pixels are sampled at their corners with a top-left fill rule, so a right
+// triangle with 10-pixel legs covers the 55 pixels with x + y < 10, and the
+// triangle across the diagonal takes the other 45 of the 10x10 square.
+static void test_fill_triangle(void) {
+    reset();
+    CHECK(fill_triangle(0, 0, 10, 0, 0, 10, COLOR_RED) == OK);
+    CHECK(count_color(COLOR_RED) == 55);
+    CHECK(read_pixel(9, 0) == COLOR_RED);
+    CHECK(read_pixel(10, 0) == 0);
+    CHECK(read_pixel(5, 5) == 0);
+
+    CHECK(fill_triangle(10, 0, 10, 10, 0, 10, COLOR_BLUE) == OK);
+    CHECK(count_color(COLOR_BLUE) == 45);
+    CHECK(count_color(COLOR_RED) == 55);
+
+    // Collinear points enclose nothing.
+    reset();
+    CHECK(fill_triangle(2, 2, 8, 8, 14, 14, COLOR_WHITE) == OK);
+    CHECK(fill_triangle(3, 7, 3, 7, 3, 7, COLOR_WHITE) == OK);
+    CHECK(count_color(0) == TEST_W * TEST_H);
+}
+
+// A 20x20 rounded rectangle with radius 5 leaves its four corner pixels
+// clear but fills the centre and the middle of every edge.
+static void test_fill_rounded_rect(void) {
+    reset();
+    CHECK(fill_rounded_rect(4, 4, 20, 20, 5, COLOR_WHITE) == OK);
+    CHECK(read_pixel(4, 4) == 0);
+    CHECK(read_pixel(23, 4) == 0);
+    CHECK(read_pixel(4, 23) == 0);
+    CHECK(read_pixel(23, 23) == 0);
+    CHECK(read_pixel(14, 14) == COLOR_WHITE);
+    CHECK(read_pixel(14, 4) == COLOR_WHITE);
+    CHECK(read_pixel(14, 23) == COLOR_WHITE);
+    CHECK(read_pixel(4, 14) == COLOR_WHITE);
+    CHECK(read_pixel(23, 14) == COLOR_WHITE);
+    CHECK(read_pixel(3, 14) == 0);
+    CHECK(read_pixel(24, 14) == 0);
+}
+
+// A 3-pixel rounded outline covers three pixels in from each straight edge
+// and leaves the inside alone.
+static void test_rounded_outline(void) {
+    reset();
+    CHECK(draw_rounded_rect_outline(4, 4, 20, 20, 5, 3, COLOR_WHITE) == OK);
+    CHECK(read_pixel(4, 14) == COLOR_WHITE);
+    CHECK(read_pixel(6, 14) == COLOR_WHITE);
+    CHECK(read_pixel(7, 14) == 0);
+    CHECK(read_pixel(14, 6) == COLOR_WHITE);
+    CHECK(read_pixel(14, 7) == 0);
+    CHECK(read_pixel(21, 14) == COLOR_WHITE);
+    CHECK(read_pixel(20, 14) == 0);
+    CHECK(read_pixel(14, 14) == 0);
+    CHECK(read_pixel(4, 4) == 0);
+}
+
+// A 10x5 ellipse outline reaches its four axis extremes and no further;
+// the filled one also covers the centre.
+static void test_ellipse(void) {
+    reset();
+    CHECK(draw_ellipse(15, 15, 10, 5, COLOR_WHITE) == OK);
+    CHECK(read_pixel(5, 15) == COLOR_WHITE);
+    CHECK(read_pixel(25, 15) == COLOR_WHITE);
+    CHECK(read_pixel(15, 10) == COLOR_WHITE);
+    CHECK(read_pixel(15, 20) == COLOR_WHITE);
+    CHECK(read_pixel(4, 15) == 0);
+    CHECK(read_pixel(15, 21) == 0);
+    CHECK(read_pixel(15, 15) == 0);
+
+    CHECK(fill_ellipse(15, 15, 10, 5, COLOR_RED) == OK);
+    CHECK(read_pixel(15, 15) == COLOR_RED);
+    CHECK(read_pixel(5, 15) == COLOR_RED);
+    CHECK(read_pixel(15, 9) == 0);
+}
+
+// A full 0..360 arc strokes exactly the circle outline.
+static void test_full_arc(void) {
+    reset();
+    draw_circle_outline(15, 14, 9, COLOR_WHITE);
+    save_screen();
+
+    reset();
+    CHECK(draw_arc(15, 14, 9, 0, 360, COLOR_WHITE) == OK);
+    CHECK(screen_matches_saved());
+    CHECK(count_color(COLOR_WHITE) > 0);
+}
+
+// A cubic whose control points all lie on one line is that line: it hits
+// both endpoints, touches every column between them and strays no more than
+// a pixel from y = 3 + (x - 2) / 2.
+static void test_collinear_cubic(void) {
+    reset();
+    CHECK(draw_bezier_cubic(2, 3, 10, 7, 18, 11, 26, 15, COLOR_WHITE) == OK);
+    CHECK(read_pixel(2, 3) == COLOR_WHITE);
+    CHECK(read_pixel(26, 15) == COLOR_WHITE);
+
+    uint32_t stray = 0, empty_cols = 0;
+    for (uint32_t x = 0; x < TEST_W; x++) {
+        uint32_t in_col = 0;
+        for (uint32_t y = 0; y < TEST_H; y++) {
+            if (read_pixel(x, y) != COLOR_WHITE) continue;
+            in_col++;
+            int64_t twice = 2 * (int64_t)y - (6 + ((int64_t)x - 2));
+            if (x < 2 || x > 26 || twice < -2 || twice > 2) stray++;
+        }
+        if (x >= 2 && x <= 26 && !in_col) empty_cols++;
+    }
+    CHECK(stray == 0);
+    CHECK(empty_cols == 0);
+}
+
+// A flood fill started inside a black border recolours the whole white
+// region and stops at the border; the white outside it is not reached.
+static void test_flood_fill_bounded(void) {
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    draw_rect_outline(4, 4, 12, 10, 1, COLOR_BLACK);
+    draw_rect(9, 5, 1, 4, COLOR_BLACK);
+
+    CHECK(flood_fill(6, 6, COLOR_RED) == OK);
+    CHECK(count_color(COLOR_RED) == 10 * 8 - 4);
+    CHECK(count_color(COLOR_BLACK) == 2 * 12 + 2 * 8 + 4);
+    CHECK(read_pixel(14, 5) == COLOR_RED);
+    CHECK(read_pixel(3, 3) == COLOR_WHITE);
+    CHECK(read_pixel(16, 8) == COLOR_WHITE);
+
+    CHECK(flood_fill(6, 6, COLOR_RED) == OK);
+    CHECK(count_color(COLOR_RED) == 10 * 8 - 4);
+}
+
+// With 2-pixel cells a 4x4 checker is four squares, the first colour at the
+// top-left and bottom-right. Zero cell and spacing sizes draw nothing.
+static void test_checker(void) {
+    reset();
+    CHECK(fill_checker(3, 5, 4, 4, 2, COLOR_WHITE, COLOR_RED) == OK);
+    for (uint32_t row = 0; row < 4; row++)
+        for (uint32_t col = 0; col < 4; col++)
+            CHECK(read_pixel(3 + col, 5 + row) ==
+                  ((row / 2 + col / 2) & 1 ? COLOR_RED : COLOR_WHITE));
+    CHECK(count_color(0) == TEST_W * TEST_H - 4 * 4);
+
+    reset();
+    CHECK(fill_checker(0, 0, 4, 4, 0, COLOR_WHITE, COLOR_RED) == OK);
+    CHECK(fill_dots(0, 0, 4, 4, 0, COLOR_WHITE) == OK);
+    CHECK(count_color(0) == TEST_W * TEST_H);
+    CHECK(fill_dots(1, 1, 5, 5, 2, COLOR_WHITE) == OK);
+    CHECK(count_color(COLOR_WHITE) == 3 * 3);
+    CHECK(read_pixel(5, 5) == COLOR_WHITE);
+}
+
+// A horizontal line of thickness 3 is three full rows centred on the line,
+// and a vertical one three full columns.
+static void test_thick_line(void) {
+    reset();
+    CHECK(draw_line_thick(4, 10, 20, 10, COLOR_WHITE, 3) == OK);
+    CHECK(count_color(COLOR_WHITE) == 3 * 17);
+    for (uint32_t x = 4; x <= 20; x++)
+        for (uint32_t y = 9; y <= 11; y++)
+            CHECK(read_pixel(x, y) == COLOR_WHITE);
+
+    reset();
+    CHECK(draw_line_thick(6, 2, 6, 12, COLOR_WHITE, 3) == OK);
+    CHECK(count_color(COLOR_WHITE) == 3 * 11);
+    CHECK(read_pixel(5, 2) == COLOR_WHITE && read_pixel(7, 12) == COLOR_WHITE);
+}
+
+// A three-point polyline draws the same pixels as the two lines joining its
+// points, and is not closed back to the first point.
+static void test_polyline(void) {
+    static const point_t points[] = { { 2, 3 }, { 20, 9 }, { 6, 27 } };
+    reset();
+    draw_line(2, 3, 20, 9, COLOR_WHITE);
+    draw_line(20, 9, 6, 27, COLOR_WHITE);
+    save_screen();
+
+    reset();
+    CHECK(draw_polyline(points, 3, COLOR_WHITE) == OK);
+    CHECK(screen_matches_saved());
+    CHECK(read_pixel(4, 15) == 0);
+}
+
+static uint32_t count_partial(void) {
+    uint32_t n = 0;
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++)
+        if (test_fb[i] != 0 && test_fb[i] != COLOR_WHITE) n++;
+    return n;
+}
+
+// A shallow line is all full-colour pixels by default; with antialiasing on,
+// draw_line() blends its edges.
+static void test_antialias_toggle(void) {
+    reset();
+    CHECK(draw_line(1, 2, 25, 11, COLOR_WHITE) == OK);
+    CHECK(count_partial() == 0);
+    CHECK(count_color(COLOR_WHITE) == 25);
+
+    reset();
+    set_antialias(1);
+    CHECK(draw_line(1, 2, 25, 11, COLOR_WHITE) == OK);
+    set_antialias(0);
+    CHECK(count_partial() > 0);
+}
+
+// A 2-pixel bevel: the top rows are light and the bottom rows dark across
+// the full width, the sides fill in between, and the middle is the fill.
+static void test_bevel_rect(void) {
+    reset();
+    CHECK(draw_bevel_rect(3, 4, 12, 10, COLOR_BLUE, COLOR_WHITE, COLOR_RED, 2) == OK);
+    for (uint32_t x = 3; x < 15; x++) {
+        CHECK(read_pixel(x, 4) == COLOR_WHITE && read_pixel(x, 5) == COLOR_WHITE);
+        CHECK(read_pixel(x, 12) == COLOR_RED && read_pixel(x, 13) == COLOR_RED);
+    }
+    CHECK(read_pixel(3, 8) == COLOR_WHITE && read_pixel(4, 8) == COLOR_WHITE);
+    CHECK(read_pixel(13, 8) == COLOR_RED && read_pixel(14, 8) == COLOR_RED);
+    CHECK(count_color(COLOR_BLUE) == 8 * 6);
+    CHECK(count_color(0) == TEST_W * TEST_H - 12 * 10);
+}
+
+// The per-row draw_circle() covers exactly the pixels the old per-pixel scan
+// did, x * x + y * y <= r * r, including when the circle is cut off by the
+// left and top edges.
+static void test_circle_matches_scan(void) {
+    static const int32_t circles[][3] = { { 10, 10, 5 }, { 20, 16, 0 }, { 2, 3, 5 }, { 16, 16, 1 } };
+    for (uint32_t i = 0; i < sizeof(circles) / sizeof(circles[0]); i++) {
+        int32_t cx = circles[i][0], cy = circles[i][1], r = circles[i][2];
+        reset();
+        for (int32_t y = -r; y <= r; y++)
+            for (int32_t x = -r; x <= r; x++)
+                if (x * x + y * y <= r * r && cx + x >= 0 && cy + y >= 0)
+                    draw_pixel((uint32_t)(cx + x), (uint32_t)(cy + y), COLOR_WHITE);
+        save_screen();
+
+        reset();
+        CHECK(draw_circle((uint32_t)cx, (uint32_t)cy, (uint32_t)r, COLOR_WHITE) == OK);
+        CHECK(screen_matches_saved());
+    }
+}
+
+// Bounds of everything drawn so far, as [x0, x1] x [y0, y1].
+static void lit_bounds(uint32_t *x0, uint32_t *x1, uint32_t *y0, uint32_t *y1) {
+    *x0 = *y0 = UINT32_MAX;
+    *x1 = *y1 = 0;
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++) {
+            if (!read_pixel(x, y)) continue;
+            if (x < *x0) *x0 = x;
+            if (x > *x1) *x1 = x;
+            if (y < *y0) *y0 = y;
+            if (y > *y1) *y1 = y;
+        }
+}
+
+// A size-12 right arrow at (16, 16) has its tip at 16 + 6 - 3 = 19 on the
+// centre row and its 13-pixel base three pixels left of centre; the other
+// directions and the chevron mirror it.
+static void test_arrow_tip(void) {
+    uint32_t x0, x1, y0, y1;
+    reset();
+    CHECK(draw_arrow(16, 16, 12, ARROW_RIGHT, COLOR_WHITE) == OK);
+    lit_bounds(&x0, &x1, &y0, &y1);
+    CHECK(x1 == 19 && x0 == 13);
+    CHECK(y0 == 10 && y1 == 22);
+    CHECK(read_pixel(19, 16) == COLOR_WHITE);
+    CHECK(read_pixel(19, 15) == 0 && read_pixel(19, 17) == 0);
+
+    reset();
+    CHECK(draw_arrow(16, 16, 12, ARROW_UP, COLOR_WHITE) == OK);
+    lit_bounds(&x0, &x1, &y0, &y1);
+    CHECK(y0 == 13 && y1 == 19 && x0 == 10 && x1 == 22);
+    CHECK(read_pixel(16, 13) == COLOR_WHITE);
+
+    reset();
+    CHECK(draw_chevron(16, 16, 12, ARROW_LEFT, COLOR_WHITE) == OK);
+    lit_bounds(&x0, &x1, &y0, &y1);
+    CHECK(x0 == 13 && x1 == 19);
+    CHECK(read_pixel(13, 16) == COLOR_WHITE);
+    CHECK(read_pixel(16, 16) == 0);
+}
+
+// A quarter turn of phase moves the brightest dot from the top of the ring
+// to the right, so the two frames differ; the same phase always draws the
+// same frame.
+static void test_spinner_phases(void) {
+    reset();
+    CHECK(draw_spinner(16, 16, 10, 0, COLOR_WHITE) == OK);
+    save_screen();
+    CHECK(red_of(read_pixel(16, 8)) > red_of(read_pixel(24, 16)));
+
+    reset();
+    CHECK(draw_spinner(16, 16, 10, 64, COLOR_WHITE) == OK);
+    CHECK(!screen_matches_saved());
+    CHECK(red_of(read_pixel(24, 16)) > red_of(read_pixel(16, 8)));
+
+    reset();
+    CHECK(draw_spinner(16, 16, 10, 0, COLOR_WHITE) == OK);
+    CHECK(screen_matches_saved());
+}
+
+void shapes_tests(void) {
+    test_aa_line_axis_aligned();
+    test_fill_triangle();
+    test_fill_rounded_rect();
+    test_rounded_outline();
+    test_ellipse();
+    test_full_arc();
+    test_collinear_cubic();
+    test_flood_fill_bounded();
+    test_checker();
+    test_thick_line();
+    test_polyline();
+    test_antialias_toggle();
+    test_bevel_rect();
+    test_circle_matches_scan();
+    test_arrow_tip();
+    test_spinner_phases();
+}
@@ -0,0 +1,68 @@
+#include "check.h"
+#include <stdlib.h>
+
+// Drawing over a snapshot and restoring it gives back the exact pixels.
+static void test_snapshot_restore(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    for (uint32_t y = 0; y < TEST_H; y++)
+        draw_rect(0, y, TEST_W, 1, color((uint8_t)y, 0, (uint8_t)(255 - y)));
+
+    uint64_t len = 0;
+    uint32_t *saved = snapshot(&len);
+    CHECK(saved != 0);
+    CHECK(len == TEST_W * TEST_H);
+    if (!saved) return;
+
+    draw_rect(4, 4, 10, 10, COLOR_WHITE);
+    CHECK(restore(saved, len) == OK);
+    uint32_t *back = display_back_buffer();
+    uint64_t mismatches = 0;
+    for (uint64_t i = 0; i < len; i++)
+        if (back[i] != saved[i]) mismatches++;
+    CHECK(mismatches == 0);
+
+    CHECK(restore(saved, len - 1) == BUFFER_TOO_SMALL);
+    CHECK(restore(saved, len + 1) == INVALID_DISPLAY_PARAMS);
+    CHECK(crossfade(saved, saved, len + 1, 128) == INVALID_DISPLAY_PARAMS);
+    free(saved);
+}
+
+// A crossfade starts on the first snapshot and ends on the second.
+static void test_crossfade(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    static uint32_t from[TEST_W * TEST_H], to[TEST_W * TEST_H];
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++) {
+        from[i] = COLOR_BLACK;
+        to[i]   = COLOR_WHITE;
+    }
+    CHECK(crossfade(from, to, TEST_W * TEST_H, 0) == OK);
+    CHECK(display_back_buffer()[0] == COLOR_BLACK);
+    CHECK(crossfade(from, to, TEST_W * TEST_H, 255) == OK);
+    CHECK(display_back_buffer()[0] == COLOR_WHITE);
+    CHECK(crossfade(from, to, TEST_W * TEST_H - 1, 128) == BUFFER_TOO_SMALL);
+}
+
+// Halfway through, every pixel is the even mix of the two snapshots.
+static void test_crossfade_midpoint(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    static uint32_t from[TEST_W * TEST_H], to[TEST_W * TEST_H];
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++) {
+        from[i] = COLOR_RED;
+        to[i]   = COLOR_BLUE;
+    }
+    CHECK(crossfade(from, to, TEST_W * TEST_H, 128) == OK);
+    uint32_t want = mix(COLOR_RED, COLOR_BLUE, 128);
+    CHECK(want != COLOR_RED && want != COLOR_BLUE);
+    CHECK(display_back_buffer()[0] == want);
+    CHECK(display_back_buffer()[TEST_W * TEST_H - 1] == want);
+    CHECK(crossfade(0, to, TEST_W * TEST_H, 128) == NULL_POINTER);
+}
+
+void snapshot_tests(void) {
+    test_snapshot_restore();
+    test_crossfade();
+    test_crossfade_midpoint();
+}
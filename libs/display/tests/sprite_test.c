@@ -0,0 +1,218 @@
+#include "check.h"
+#include <string.h>
+
+// A 2x2 indexed image against a three-colour palette: each index picks its
+// palette entry, and index 3, past the colours given, is transparent.
+static void test_indexed(void) {
+    reset();
+    static const uint32_t colors[] = { COLOR_RED, COLOR_GREEN, COLOR_BLUE };
+    static const uint8_t  indices[] = { 0, 1, 2, 3 };
+    uint32_t palette[256];
+    CHECK(palette_from(palette, colors, 3) == OK);
+    CHECK(palette[2] == COLOR_BLUE);
+    CHECK(palette[3] == 0);
+
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_WHITE);
+    CHECK(draw_indexed(5, 6, 2, 2, indices, sizeof(indices), palette) == OK);
+    CHECK(read_pixel(5, 6) == COLOR_RED);
+    CHECK(read_pixel(6, 6) == COLOR_GREEN);
+    CHECK(read_pixel(5, 7) == COLOR_BLUE);
+    CHECK(read_pixel(6, 7) == COLOR_WHITE);
+    CHECK(draw_indexed(5, 6, 2, 2, indices, 3, palette) == BUFFER_TOO_SMALL);
+
+    CHECK(palette_from(0, colors, 3) == NULL_POINTER);
+    CHECK(palette_from(palette, 0, 3) == NULL_POINTER);
+    CHECK(palette_from(palette, 0, 0) == OK);
+    CHECK(palette[0] == 0);
+}
+
+// A 2x2 sprite with one fully transparent pixel: the three opaque ones are
+// copied and the background shows through the fourth.
+static void test_sprite_transparency(void) {
+    reset();
+    static const uint32_t sprite[] = { COLOR_RED, 0x00FFFFFF, COLOR_GREEN, COLOR_BLUE };
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_GRAY);
+    CHECK(draw_sprite(3, 3, 2, 2, sprite) == OK);
+    CHECK(read_pixel(3, 3) == COLOR_RED);
+    CHECK(read_pixel(4, 3) == COLOR_GRAY);
+    CHECK(read_pixel(3, 4) == COLOR_GREEN);
+    CHECK(read_pixel(4, 4) == COLOR_BLUE);
+}
+
+// Nearest-neighbour doubling turns one red pixel into a 2x2 red block.
+static void test_scaled_nearest(void) {
+    reset();
+    static const uint32_t red = COLOR_RED;
+    CHECK(draw_sprite_scaled(6, 7, 1, 1, &red, 2, 2) == OK);
+    CHECK(read_pixel(6, 7) == COLOR_RED);
+    CHECK(read_pixel(7, 7) == COLOR_RED);
+    CHECK(read_pixel(6, 8) == COLOR_RED);
+    CHECK(read_pixel(7, 8) == COLOR_RED);
+    CHECK(count_color(COLOR_RED) == 4);
+}
+
+// Enlarging a black-to-white pair 4x samples pixel centres, so the ends
+// clamp to the two source colours and the two middle pixels sit a quarter
+// either side of mid-grey.
+static void test_scaled_bilinear(void) {
+    reset();
+    static const uint32_t ramp[] = { COLOR_BLACK, COLOR_WHITE };
+    static const uint8_t expected[8] = { 0x00, 0x00, 0x1F, 0x5F, 0x9F, 0xDF, 0xFF, 0xFF };
+    CHECK(draw_sprite_scaled_bilinear(0, 0, 2, 1, ramp, 8, 4) == OK);
+    for (uint32_t y = 0; y < 4; y++)
+        for (uint32_t x = 0; x < 8; x++)
+            CHECK(read_pixel(x, y) == color(expected[x], expected[x], expected[x]));
+}
+
+static const uint32_t pair[] = { COLOR_RED, COLOR_BLUE };
+
+// Flipping a 2x1 sprite horizontally swaps its two pixels.
+static void test_flip_h(void) {
+    reset();
+    CHECK(draw_sprite_flipped(2, 2, 2, 1, pair, 1, 0) == OK);
+    CHECK(read_pixel(2, 2) == COLOR_BLUE);
+    CHECK(read_pixel(3, 2) == COLOR_RED);
+    CHECK(draw_sprite_flipped(2, 4, 2, 1, pair, 0, 0) == OK);
+    CHECK(read_pixel(2, 4) == COLOR_RED);
+    CHECK(read_pixel(3, 4) == COLOR_BLUE);
+}
+
+// A quarter turn clockwise stands the 2x1 sprite up as a 1x2 column with
+// its left pixel on top; three quarter turns put it at the bottom.
+static void test_rotate90(void) {
+    reset();
+    CHECK(draw_sprite_rotated90(5, 5, 2, 1, pair, 1) == OK);
+    CHECK(read_pixel(5, 5) == COLOR_RED);
+    CHECK(read_pixel(5, 6) == COLOR_BLUE);
+    CHECK(read_pixel(6, 5) == 0);
+
+    CHECK(draw_sprite_rotated90(9, 5, 2, 1, pair, 3) == OK);
+    CHECK(read_pixel(9, 5) == COLOR_BLUE);
+    CHECK(read_pixel(9, 6) == COLOR_RED);
+    CHECK(count_color(COLOR_RED) == 2);
+}
+
+// Rotating by 0 degrees is a plain blit with the sprite centred on (cx, cy),
+// and rotating by 180 is the same as flipping both ways.
+static void test_rotate_any_angle(void) {
+    static uint32_t sprite[4 * 3], expected[TEST_W * TEST_H];
+    for (uint32_t i = 0; i < 4 * 3; i++) sprite[i] = color((uint8_t)(i + 1), 0, 0);
+
+    reset();
+    draw_sprite(8, 9, 4, 3, sprite);
+    memcpy(expected, test_fb, sizeof(expected));
+    reset();
+    CHECK(draw_sprite_rotated(10, 10, 4, 3, sprite, 0) == OK);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+
+    reset();
+    draw_sprite_flipped(8, 9, 4, 3, sprite, 1, 1);
+    memcpy(expected, test_fb, sizeof(expected));
+    reset();
+    CHECK(draw_sprite_rotated(10, 10, 4, 3, sprite, 180) == OK);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+}
+
+// Erasing a cursor puts the background back bit for bit, including when the
+// cursor hangs off the corner of the screen.
+static void test_cursor_restores_background(void) {
+    static uint32_t background[TEST_W * TEST_H];
+    static const uint32_t arrow[4 * 4] = {
+        COLOR_WHITE, COLOR_WHITE, COLOR_WHITE, 0x80FFFFFF,
+        COLOR_WHITE, COLOR_WHITE, 0x80FFFFFF, 0,
+        COLOR_WHITE, 0x80FFFFFF, 0,           0,
+        0x80FFFFFF,  0,           0,           0,
+    };
+    reset();
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++)
+            draw_pixel(x, y, color((uint8_t)(x * 8), (uint8_t)(y * 8), 0x40));
+    memcpy(background, test_fb, sizeof(background));
+
+    CHECK(draw_cursor(5, 6, 4, 4, arrow) == OK);
+    CHECK(read_pixel(5, 6) == COLOR_WHITE);
+    CHECK(read_pixel(8, 9) == background[9 * TEST_W + 8]);
+    CHECK(erase_cursor() == OK);
+    CHECK(memcmp(background, test_fb, sizeof(background)) == 0);
+
+    CHECK(draw_cursor(TEST_W - 2, -1, 4, 4, arrow) == OK);
+    CHECK(read_pixel(TEST_W - 2, 0) == COLOR_WHITE);
+    CHECK(memcmp(background, test_fb, sizeof(background)) != 0);
+    CHECK(erase_cursor() == OK);
+    CHECK(memcmp(background, test_fb, sizeof(background)) == 0);
+}
+
+// An opaque mask paints on bits in fg and off bits in bg, where the plain
+// mask leaves off bits showing what was underneath. Rows are byte-padded.
+static void test_mask_bg(void) {
+    static const uint8_t bits[2] = { 0xA0, 0x40 };  // 10100000, 01000000
+    reset();
+    draw_rect(0, 0, TEST_W, TEST_H, COLOR_BLUE);
+    CHECK(draw_mask_bg(1, 1, 3, 2, bits, sizeof(bits), COLOR_WHITE, COLOR_RED) == OK);
+    CHECK(read_pixel(1, 1) == COLOR_WHITE);
+    CHECK(read_pixel(2, 1) == COLOR_RED);
+    CHECK(read_pixel(3, 1) == COLOR_WHITE);
+    CHECK(read_pixel(1, 2) == COLOR_RED);
+    CHECK(read_pixel(2, 2) == COLOR_WHITE);
+    CHECK(read_pixel(3, 2) == COLOR_RED);
+    CHECK(read_pixel(4, 1) == COLOR_BLUE);
+
+    CHECK(draw_mask(5, 1, 3, 2, bits, sizeof(bits), COLOR_WHITE) == OK);
+    CHECK(read_pixel(5, 1) == COLOR_WHITE);
+    CHECK(read_pixel(6, 1) == COLOR_BLUE);
+    CHECK(draw_mask_bg(0, 0, 3, 2, bits, 1, COLOR_WHITE, COLOR_RED) == BUFFER_TOO_SMALL);
+}
+
+// A 3x3 nine-patch with 1-pixel insets drawn at 5x5: the corners stay where
+// they are, the edges repeat their middle pixel along their length and the
+// centre pixel fills the 3x3 middle.
+static void test_nine_patch(void) {
+    uint32_t src[3 * 3];
+    for (uint32_t i = 0; i < 3 * 3; i++) src[i] = color((uint8_t)(i * 20 + 10), 0, 0xFF);
+    reset();
+    CHECK(draw_nine_patch(2, 2, 5, 5, src, 3, 3, 1, 1, 1, 1) == OK);
+    for (uint32_t row = 0; row < 5; row++) {
+        uint32_t sy = row == 0 ? 0 : row == 4 ? 2 : 1;
+        for (uint32_t col = 0; col < 5; col++) {
+            uint32_t sx = col == 0 ? 0 : col == 4 ? 2 : 1;
+            CHECK(read_pixel(2 + col, 2 + row) == src[sy * 3 + sx]);
+        }
+    }
+    CHECK(count_color(0) == TEST_W * TEST_H - 5 * 5);
+    CHECK(draw_nine_patch(0, 0, 5, 5, src, 3, 3, 2, 2, 1, 1) == INVALID_DISPLAY_PARAMS);
+}
+
+// A 2x2 tile over a 5x5 area repeats every two pixels, so the last row and
+// column are the tile's first again, cut off by the area's edge. An offset
+// of one shifts the pattern by a pixel and wraps it round.
+static void test_tiled(void) {
+    static const uint32_t tile[2 * 2] = { COLOR_RED, COLOR_BLUE, COLOR_WHITE, COLOR_CORAL };
+    reset();
+    CHECK(draw_tiled(3, 4, 5, 5, tile, 2, 2, 0, 0) == OK);
+    for (uint32_t row = 0; row < 5; row++)
+        for (uint32_t col = 0; col < 5; col++)
+            CHECK(read_pixel(3 + col, 4 + row) == tile[(row % 2) * 2 + col % 2]);
+    CHECK(count_color(0) == TEST_W * TEST_H - 5 * 5);
+
+    reset();
+    CHECK(draw_tiled(3, 4, 5, 5, tile, 2, 2, 1, -1) == OK);
+    CHECK(read_pixel(3, 4) == COLOR_CORAL);
+    CHECK(read_pixel(4, 4) == COLOR_WHITE);
+    CHECK(read_pixel(3, 5) == COLOR_BLUE);
+    CHECK(read_pixel(7, 8) == COLOR_CORAL);
+    CHECK(draw_tiled(0, 0, 5, 5, tile, 0, 2, 0, 0) == INVALID_DISPLAY_PARAMS);
+}
+
+void sprite_tests(void) {
+    test_indexed();
+    test_sprite_transparency();
+    test_scaled_nearest();
+    test_scaled_bilinear();
+    test_flip_h();
+    test_rotate90();
+    test_rotate_any_angle();
+    test_cursor_restores_background();
+    test_mask_bg();
+    test_nine_patch();
+    test_tiled();
+}
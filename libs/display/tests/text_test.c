@@ -0,0 +1,132 @@
+#include "check.h"
+#include <string.h>
+
+// "Hi" in the built-in font: 'H' has its two uprights in columns 0-1 and
+// 4-5 joined on row 3, and the dot of the 'i' sits in columns 2-3 of the
+// second cell with a blank row under it.
+static void test_draw_text_hi(void) {
+    reset();
+    CHECK(draw_text(0, 0, "Hi", COLOR_WHITE) == 2 * FONT_WIDTH);
+    CHECK(read_pixel(0, 0) == COLOR_WHITE);
+    CHECK(read_pixel(5, 6) == COLOR_WHITE);
+    CHECK(read_pixel(2, 0) == 0);
+    CHECK(read_pixel(2, 3) == COLOR_WHITE);
+    CHECK(read_pixel(FONT_WIDTH + 2, 0) == COLOR_WHITE);
+    CHECK(read_pixel(FONT_WIDTH + 3, 0) == COLOR_WHITE);
+    CHECK(read_pixel(FONT_WIDTH + 2, 1) == 0);
+    CHECK(read_pixel(0, 7) == 0);
+}
+
+// Fractional tracking accumulates: a quarter pixel between each of nine
+// glyphs widens the string by two pixels, 85/256 over twelve gaps by four,
+// and negative tracking narrows it by the same amount.
+static void test_tracking_width(void) {
+    reset();
+    CHECK(draw_text_tracked(0, 0, "iiiiiiiii", COLOR_WHITE, 0) == 9 * FONT_WIDTH);
+    CHECK(draw_text_tracked(0, 0, "iiiiiiiii", COLOR_WHITE, 64) == 9 * FONT_WIDTH + 2);
+    CHECK(draw_text_tracked(0, 0, "iiiiiiiii", COLOR_WHITE, -64) == 9 * FONT_WIDTH - 2);
+    CHECK(draw_text_tracked(0, 0, "iiiiiiiiiiiii", COLOR_WHITE, 85) == 13 * FONT_WIDTH + 4);
+
+    reset();
+    draw_text_tracked(0, 0, "ii", COLOR_WHITE, 256);
+    CHECK(read_pixel(2, 0) == COLOR_WHITE);
+    CHECK(read_pixel(FONT_WIDTH + 2, 0) == 0);
+    CHECK(read_pixel(FONT_WIDTH + 3, 0) == COLOR_WHITE);
+}
+
+// A two-line string measures as its wider line by two line heights, without
+// drawing anything.
+static void test_measure_two_lines(void) {
+    uint32_t w = 0, h = 0;
+    reset();
+    measure_text_builtin("ab\nwxyz", &w, &h);
+    CHECK(w == 4 * FONT_WIDTH && h == 2 * FONT_HEIGHT);
+    measure_text_builtin("wxyz\nab", &w, &h);
+    CHECK(w == 4 * FONT_WIDTH && h == 2 * FONT_HEIGHT);
+    measure_text_builtin("ab\n", &w, &h);
+    CHECK(w == 2 * FONT_WIDTH && h == 2 * FONT_HEIGHT);
+    measure_text_builtin("", &w, &h);
+    CHECK(w == 0 && h == 0);
+    CHECK(count_color(0) == TEST_W * TEST_H);
+}
+
+// In a three-column box each word of "to be or not" gets a line of its own,
+// nothing is drawn past the box, and a run of spaces that fits is kept.
+static void test_wrapped_text(void) {
+    reset();
+    CHECK(draw_text_wrapped(0, 0, 3 * FONT_WIDTH, "to be or not", COLOR_WHITE) == 4 * FONT_HEIGHT);
+    uint32_t past = 0;
+    uint32_t lines[4] = { 0, 0, 0, 0 };
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++) {
+            if (read_pixel(x, y) != COLOR_WHITE) continue;
+            if (x >= 3 * FONT_WIDTH) past++;
+            lines[y / FONT_HEIGHT]++;
+        }
+    CHECK(past == 0);
+    CHECK(lines[0] && lines[1] && lines[2] && lines[3]);
+
+    reset();
+    CHECK(draw_text_wrapped(0, 0, 4 * FONT_WIDTH, "a  b", COLOR_WHITE) == FONT_HEIGHT);
+    CHECK(draw_text_wrapped(0, 0, 4 * FONT_WIDTH, "a\nb", COLOR_WHITE) == 2 * FONT_HEIGHT);
+    CHECK(draw_text_wrapped(0, 0, 2 * FONT_WIDTH, "abcde", COLOR_WHITE) == 3 * FONT_HEIGHT);
+}
+
+// Centred text lands at (w - text_width) / 2 across and (h - text_height) / 2
+// down, the same pixels draw_text() gives there; right-aligned text ends at
+// the right edge. Text wider than the box is cut off at its edges.
+static void test_centered_offset(void) {
+    static uint32_t expected[TEST_W * TEST_H];
+    rect_t r = { 1, 3, 30, 12 };
+    uint32_t tw, th;
+    measure_text_builtin("Hi", &tw, &th);
+
+    reset();
+    draw_text(r.x + (r.w - tw) / 2, r.y + (r.h - th) / 2, "Hi", COLOR_WHITE);
+    memcpy(expected, test_fb, sizeof(expected));
+    reset();
+    CHECK(draw_text_centered(r, "Hi", COLOR_WHITE) == OK);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+
+    reset();
+    draw_text(r.x + r.w - tw, r.y + (r.h - th) / 2, "Hi", COLOR_WHITE);
+    memcpy(expected, test_fb, sizeof(expected));
+    reset();
+    CHECK(draw_text_right(r, "Hi", COLOR_WHITE) == OK);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+
+    reset();
+    CHECK(draw_text_centered((rect_t){ 4, 4, 12, 8 }, "HHHH", COLOR_WHITE) == OK);
+    uint32_t outside = 0;
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++)
+            if (read_pixel(x, y) && !rect_contains((rect_t){ 4, 4, 12, 8 }, x, y)) outside++;
+    CHECK(outside == 0);
+}
+
+// The shadow is the same glyphs moved down and right by the offset, with the
+// main text drawn over it: the top-left of the 'H' stays the text colour and
+// its copy two pixels further on shows in the gap between the uprights.
+static void test_text_shadow(void) {
+    static uint32_t expected[TEST_W * TEST_H];
+    reset();
+    draw_text(6, 6, "Hi", COLOR_RED);
+    draw_text(4, 4, "Hi", COLOR_WHITE);
+    memcpy(expected, test_fb, sizeof(expected));
+
+    reset();
+    CHECK(draw_text_shadow(4, 4, "Hi", COLOR_WHITE, COLOR_RED, 2) == 2 * FONT_WIDTH);
+    CHECK(memcmp(expected, test_fb, sizeof(expected)) == 0);
+    CHECK(read_pixel(4, 4) == COLOR_WHITE);
+    CHECK(read_pixel(6, 6) == COLOR_RED);
+    CHECK(count_color(COLOR_RED) > 0);
+}
+
+void text_tests(void) {
+    test_draw_text_hi();
+    test_tracking_width();
+    test_measure_two_lines();
+    test_wrapped_text();
+    test_centered_offset();
+    test_text_shadow();
+}
@@ -0,0 +1,81 @@
+#pragma once
+#include <stdint.h>
+#include <display.h>
+
+// Immediate-mode widgets drawn with the display library. Each call draws the
+// widget and reports what the mouse did to it this frame; there is no retained
+// widget tree. The caller fills in the mouse state from its own input source.
+
+typedef struct {
+    int32_t x;
+    int32_t y;
+    uint8_t left_down;      // held this frame
+    uint8_t left_pressed;   // went down this frame
+    uint8_t left_released;  // came up this frame
+} ui_mouse_t;
+
+typedef struct {
+    uint8_t code;      // key code as delivered in input_event_t
+    char    ch;        // character it produces, or 0
+    uint8_t pressed;   // 1 for a press, 0 for a release
+    uint8_t scancode;  // scancode from input_event_t, for keys with no character
+} ui_key_t;
+
+// Set 1 scancodes of the keys that move the caret in a text field.
+#define UI_SCAN_HOME  0x47
+#define UI_SCAN_LEFT  0x4B
+#define UI_SCAN_RIGHT 0x4D
+#define UI_SCAN_END   0x4F
+
+int  ui_mouse_over(const ui_mouse_t *mouse, rect_t r);
+void ui_mouse_update(ui_mouse_t *mouse, int32_t x, int32_t y, int left_down);
+
+typedef struct {
+    rect_t content;  // inside the border, below the title bar
+    rect_t close;    // the close button, for hit-testing
+} ui_window_t;
+
+// Colours shared by all widgets. Every widget has a _themed variant taking
+// one explicitly; the plain functions use the default set by ui_set_theme(),
+// except ui_progress_bar(), which takes its two colours as arguments.
+typedef struct {
+    uint32_t background;  // window content
+    uint32_t face;        // buttons and knobs
+    uint32_t face_hover;
+    uint32_t face_down;
+    uint32_t border;
+    uint32_t text;
+    uint32_t accent;      // selection, focus and "on" state
+    uint32_t highlight;   // hovered list rows
+    uint32_t track;       // slider and toggle tracks
+    uint32_t field;       // text fields and lists
+    uint32_t close;       // close button on an active window
+} ui_theme_t;
+
+ui_theme_t        ui_theme_dark(void);
+ui_theme_t        ui_theme_light(void);
+void              ui_set_theme(const ui_theme_t *theme);
+const ui_theme_t *ui_theme(void);
+
+void     ui_begin_frame(void);
+fb_error ui_end_frame(void);
+
+int         ui_button(rect_t r, const char *label, const ui_mouse_t *mouse);
+int         ui_checkbox(rect_t r, int *checked, const ui_mouse_t *mouse);
+int         ui_toggle(rect_t r, int *on, const ui_mouse_t *mouse);
+int         ui_slider(rect_t r, uint8_t *value, const ui_mouse_t *mouse);
+int         ui_scrollbar_v(rect_t r, uint32_t content_h, uint32_t view_h, uint32_t *scroll, const ui_mouse_t *mouse);
+void        ui_progress_bar(rect_t r, uint8_t fraction, uint32_t fill, uint32_t bg, int rounded);
+int         ui_text_field(rect_t r, char *buf, uint32_t cap, uint32_t *caret, int focused, int show_caret, const ui_key_t *key);
+int32_t     ui_list(rect_t r, const char *const *items, uint32_t count, uint32_t *selected, const ui_mouse_t *mouse);
+ui_window_t ui_window_frame(rect_t r, const char *title, int focused);
+
+int         ui_button_themed(const ui_theme_t *t, rect_t r, const char *label, const ui_mouse_t *mouse);
+int         ui_checkbox_themed(const ui_theme_t *t, rect_t r, int *checked, const ui_mouse_t *mouse);
+int         ui_toggle_themed(const ui_theme_t *t, rect_t r, int *on, const ui_mouse_t *mouse);
+int         ui_slider_themed(const ui_theme_t *t, rect_t r, uint8_t *value, const ui_mouse_t *mouse);
+int         ui_scrollbar_v_themed(const ui_theme_t *t, rect_t r, uint32_t content_h, uint32_t view_h, uint32_t *scroll, const ui_mouse_t *mouse);
+void        ui_progress_bar_themed(const ui_theme_t *t, rect_t r, uint8_t fraction, int rounded);
+int         ui_text_field_themed(const ui_theme_t *t, rect_t r, char *buf, uint32_t cap, uint32_t *caret, int focused, int show_caret, const ui_key_t *key);
+int32_t     ui_list_themed(const ui_theme_t *t, rect_t r, const char *const *items, uint32_t count, uint32_t *selected, const ui_mouse_t *mouse);
+ui_window_t ui_window_frame_themed(const ui_theme_t *t, rect_t r, const char *title, int focused);
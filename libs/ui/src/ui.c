@@ -0,0 +1,420 @@
+#include <stdint.h>
+#include <string.h>
+#include <display.h>
+#include <ui.h>
+
+#define UI_PAD         3
+#define UI_ROW_H       (FONT_HEIGHT + 2 * UI_PAD)
+#define UI_TITLE_H     (FONT_HEIGHT + 2 * UI_PAD + 2)
+
+ui_theme_t ui_theme_dark(void) {
+    return (ui_theme_t){
+        .background = 0xFF202020, .face   = 0xFF3C3C3C, .face_hover = 0xFF505050,
+        .face_down  = 0xFF2A2A2A, .border = 0xFF808080, .text       = 0xFFFFFFFF,
+        .accent     = 0xFF3D7EDB, .highlight = 0xFF454545, .track   = 0xFF2A2A2A,
+        .field      = 0xFF1E1E1E, .close  = 0xFFC42B1C,
+    };
+}
+
+ui_theme_t ui_theme_light(void) {
+    return (ui_theme_t){
+        .background = 0xFFF3F3F3, .face   = 0xFFE1E1E1, .face_hover = 0xFFE5F1FB,
+        .face_down  = 0xFFCCE4F7, .border = 0xFF7A7A7A, .text       = 0xFF000000,
+        .accent     = 0xFF0078D7, .highlight = 0xFFD8E6F2, .track   = 0xFFC8C8C8,
+        .field      = 0xFFFFFFFF, .close  = 0xFFE81123,
+    };
+}
+
+// Widgets mark the rectangle they draw as dirty, so a frame bracketed by
+// ui_begin_frame() and ui_end_frame() presents only what the widgets covered.
+void ui_begin_frame(void) {
+    clear_dirty();
+}
+
+fb_error ui_end_frame(void) {
+    return swap_dirty();
+}
+
+// Used by the widget functions that don't take a theme.
+static ui_theme_t theme;
+static int        theme_set = 0;
+
+void ui_set_theme(const ui_theme_t *t) {
+    if (!t) return;
+    theme     = *t;
+    theme_set = 1;
+}
+
+// The current default theme; dark until ui_set_theme() says otherwise.
+const ui_theme_t *ui_theme(void) {
+    if (!theme_set) {
+        theme     = ui_theme_dark();
+        theme_set = 1;
+    }
+    return &theme;
+}
+
+// The widget the mouse went down on, identified by its rectangle. A click is
+// a release over the same widget that took the press.
+static int    active = 0;
+static rect_t active_rect;
+
+int ui_mouse_over(const ui_mouse_t *m, rect_t r) {
+    return m && m->x >= 0 && m->y >= 0 && rect_contains(r, (uint32_t)m->x, (uint32_t)m->y);
+}
+
+// Advance the mouse state by one frame from the pointer position and whether
+// the left button is held now; the pressed/released edges are derived from the
+// previous frame's state.
+void ui_mouse_update(ui_mouse_t *m, int32_t x, int32_t y, int left_down) {
+    if (!m) return;
+    uint8_t down = left_down != 0;
+    m->left_pressed  = down && !m->left_down;
+    m->left_released = !down && m->left_down;
+    m->left_down     = down;
+    m->x = x;
+    m->y = y;
+}
+
+static int same_rect(rect_t a, rect_t b) {
+    return a.x == b.x && a.y == b.y && a.w == b.w && a.h == b.h;
+}
+
+// Track press and release for the widget at r. Returns 1 on a click.
+static int interact(rect_t r, const ui_mouse_t *m, int *held) {
+    int over = ui_mouse_over(m, r);
+    if (over && m->left_pressed) {
+        active      = 1;
+        active_rect = r;
+    }
+    int mine = active && same_rect(active_rect, r);
+    *held = mine && m && m->left_down;
+    if (mine && m && m->left_released) {
+        active = 0;
+        return over;
+    }
+    return 0;
+}
+
+// A push button. Returns 1 on the frame the mouse is released over it after
+// having been pressed on it.
+int ui_button_themed(const ui_theme_t *t, rect_t r, const char *label, const ui_mouse_t *mouse) {
+    if (!t) t = ui_theme();
+    int held;
+    int clicked = interact(r, mouse, &held);
+    uint32_t face = held ? t->face_down : ui_mouse_over(mouse, r) ? t->face_hover : t->face;
+
+    mark_dirty_rect(r);
+    draw_rect(r.x, r.y, r.w, r.h, face);
+    if (r.w >= 2 && r.h >= 2)
+        draw_rect_outline(r.x, r.y, r.w, r.h, 1, t->border);
+    if (label) draw_text_centered(r, label, t->text);
+    return clicked;
+}
+
+int ui_button(rect_t r, const char *label, const ui_mouse_t *mouse) {
+    return ui_button_themed(ui_theme(), r, label, mouse);
+}
+
+// A check box filling r, ticked while *checked is set. A click flips it;
+// returns 1 on the frame it changed.
+int ui_checkbox_themed(const ui_theme_t *t, rect_t r, int *checked, const ui_mouse_t *mouse) {
+    if (!t) t = ui_theme();
+    if (!checked) return 0;
+    int held;
+    int changed = interact(r, mouse, &held);
+    if (changed) *checked = !*checked;
+
+    mark_dirty_rect(r);
+    draw_rect(r.x, r.y, r.w, r.h, held ? t->face_down : t->face);
+    if (r.w >= 2 && r.h >= 2)
+        draw_rect_outline(r.x, r.y, r.w, r.h, 1, ui_mouse_over(mouse, r) ? t->accent : t->border);
+    if (*checked && r.w >= 6 && r.h >= 6) {
+        // Two strokes, each doubled one pixel down so the tick reads at small sizes.
+        uint32_t x0 = r.x + r.w / 4,     y0 = r.y + r.h / 2;
+        uint32_t x1 = r.x + r.w * 2 / 5, y1 = r.y + r.h * 3 / 4 - 1;
+        uint32_t x2 = r.x + r.w * 3 / 4, y2 = r.y + r.h / 4;
+        for (uint32_t d = 0; d < 2; d++) {
+            draw_line(x0, y0 + d, x1, y1 + d, t->text);
+            draw_line(x1, y1 + d, x2, y2 + d, t->text);
+        }
+    }
+    return changed;
+}
+
+int ui_checkbox(rect_t r, int *checked, const ui_mouse_t *mouse) {
+    return ui_checkbox_themed(ui_theme(), r, checked, mouse);
+}
+
+// An on/off switch: a rounded track with a knob at the left (off) or right
+// (on) end. Behaves like ui_checkbox().
+int ui_toggle_themed(const ui_theme_t *t, rect_t r, int *on, const ui_mouse_t *mouse) {
+    if (!t) t = ui_theme();
+    if (!on) return 0;
+    int held;
+    int changed = interact(r, mouse, &held);
+    if (changed) *on = !*on;
+    if (r.w < 4 || r.h < 4) return changed;
+
+    uint32_t track = *on ? t->accent : t->track;
+    if (ui_mouse_over(mouse, r)) track = lighten(track, 32);
+    mark_dirty_rect(r);
+    fill_rounded_rect(r.x, r.y, r.w, r.h, r.h / 2, track);
+
+    uint32_t knob = r.h / 2 - 2;
+    uint32_t cx = *on ? r.x + r.w - r.h / 2 : r.x + r.h / 2;
+    draw_circle(cx, r.y + r.h / 2, knob, held ? darken(t->text, 32) : t->text);
+    return changed;
+}
+
+int ui_toggle(rect_t r, int *on, const ui_mouse_t *mouse) {
+    return ui_toggle_themed(ui_theme(), r, on, mouse);
+}
+
+// A horizontal slider over 0..255. Pressing on it grabs the knob, which then
+// follows the mouse (clamped to the track) until release, even if the pointer
+// leaves the widget. Returns 1 on the frames *value changed.
+int ui_slider_themed(const ui_theme_t *t, rect_t r, uint8_t *value, const ui_mouse_t *mouse) {
+    if (!t) t = ui_theme();
+    if (!value) return 0;
+    int held;
+    interact(r, mouse, &held);
+    if (r.w < 4 || r.h < 4) return 0;
+
+    uint32_t knob_w = r.h / 2 < r.w ? r.h / 2 : r.w, travel = r.w - knob_w;
+    int changed = 0;
+    if (held) {
+        int64_t pos = (int64_t)mouse->x - r.x - knob_w / 2;
+        int64_t v   = pos <= 0 ? 0 : pos >= travel ? 255 : pos * 255 / travel;
+        changed = v != *value;
+        *value  = (uint8_t)v;
+    }
+
+    uint32_t knob_x  = r.x + (uint32_t)((uint64_t)*value * travel / 255);
+    uint32_t track_h = r.h / 4 ? r.h / 4 : 1;
+    uint32_t track_y = r.y + (r.h - track_h) / 2;
+    mark_dirty_rect(r);
+    draw_rect(r.x, track_y, r.w, track_h, t->track);
+    draw_rect(r.x, track_y, knob_x - r.x + knob_w / 2, track_h, t->accent);
+
+    uint32_t knob = held ? t->face_down : ui_mouse_over(mouse, r) ? t->face_hover : t->face;
+    draw_rect(knob_x, r.y, knob_w, r.h, knob);
+    draw_rect_outline(knob_x, r.y, knob_w, r.h, 1, t->border);
+    return changed;
+}
+
+int ui_slider(rect_t r, uint8_t *value, const ui_mouse_t *mouse) {
+    return ui_slider_themed(ui_theme(), r, value, mouse);
+}
+
+// Where inside the scrollbar thumb the mouse took hold of it, so dragging
+// doesn't make the thumb jump to be centred on the pointer.
+static int32_t grab_dy = 0;
+
+// A vertical scrollbar for a view showing view_h of content_h units, scrolled
+// down by *scroll. The thumb gets view_h / content_h of the track; dragging it
+// moves *scroll, and pressing the track elsewhere centres the thumb there
+// first. *scroll is clamped to 0..content_h - view_h. Returns 1 if it changed.
+int ui_scrollbar_v_themed(const ui_theme_t *t, rect_t r, uint32_t content_h, uint32_t view_h,
+                          uint32_t *scroll, const ui_mouse_t *mouse) {
+    if (!t) t = ui_theme();
+    if (!scroll) return 0;
+    uint32_t max = content_h > view_h ? content_h - view_h : 0;
+    uint32_t old = *scroll;
+    if (*scroll > max) *scroll = max;
+    if (rect_is_empty(r)) return *scroll != old;
+
+    uint32_t thumb_h = max ? (uint32_t)((uint64_t)r.h * view_h / content_h) : r.h;
+    if (thumb_h < r.w) thumb_h = r.w < r.h ? r.w : r.h;
+    uint32_t travel  = r.h - thumb_h;
+    uint32_t thumb_y = r.y + (max ? (uint32_t)((uint64_t)*scroll * travel / max) : 0);
+
+    int held;
+    int pressed = ui_mouse_over(mouse, r) && mouse->left_pressed;
+    interact(r, mouse, &held);
+    if (pressed) {
+        rect_t thumb = { r.x, thumb_y, r.w, thumb_h };
+        grab_dy = ui_mouse_over(mouse, thumb) ? mouse->y - (int32_t)thumb_y : (int32_t)(thumb_h / 2);
+    }
+    if (held && max) {
+        int64_t pos = (int64_t)mouse->y - r.y - grab_dy;
+        *scroll = pos <= 0 ? 0 : pos >= travel ? max : (uint32_t)((uint64_t)pos * max / travel);
+        thumb_y = r.y + (uint32_t)((uint64_t)*scroll * travel / max);
+    }
+
+    mark_dirty_rect(r);
+    draw_rect(r.x, r.y, r.w, r.h, t->track);
+    uint32_t face = held ? t->face_down : ui_mouse_over(mouse, r) ? t->face_hover : t->face;
+    draw_rect(r.x, thumb_y, r.w, thumb_h, face);
+    if (r.w >= 2 && thumb_h >= 2)
+        draw_rect_outline(r.x, thumb_y, r.w, thumb_h, 1, t->border);
+    return *scroll != old;
+}
+
+int ui_scrollbar_v(rect_t r, uint32_t content_h, uint32_t view_h, uint32_t *scroll, const ui_mouse_t *mouse) {
+    return ui_scrollbar_v_themed(ui_theme(), r, content_h, view_h, scroll, mouse);
+}
+
+// A bar filled fraction/255 of the way from the left. With `rounded` the
+// track and the filled part get semicircular ends.
+void ui_progress_bar(rect_t r, uint8_t fraction, uint32_t fill, uint32_t bg, int rounded) {
+    if (rect_is_empty(r)) return;
+    mark_dirty_rect(r);
+    uint32_t w = (uint32_t)((uint64_t)r.w * fraction / 255);
+    if (rounded) {
+        fill_rounded_rect(r.x, r.y, r.w, r.h, r.h / 2, bg);
+        if (w) fill_rounded_rect(r.x, r.y, w, r.h, r.h / 2, fill);
+    } else {
+        draw_rect(r.x + w, r.y, r.w - w, r.h, bg);
+        if (w) draw_rect(r.x, r.y, w, r.h, fill);
+    }
+}
+
+// ui_progress_bar() in the theme's accent over its track colour.
+void ui_progress_bar_themed(const ui_theme_t *t, rect_t r, uint8_t fraction, int rounded) {
+    if (!t) t = ui_theme();
+    ui_progress_bar(r, fraction, t->accent, t->track, rounded);
+}
+
+// Apply one key to a NUL-terminated buffer of `cap` bytes: printable
+// characters are inserted at the caret, backspace deletes before it, and the
+// arrow, Home and End keys move the caret without changing the text.
+static int edit_text(char *buf, uint32_t cap, uint32_t *caret, const ui_key_t *key) {
+    uint32_t len = (uint32_t)strlen(buf);
+    if (*caret > len) *caret = len;
+
+    if (!key->ch) {
+        switch (key->scancode) {
+            case UI_SCAN_LEFT:  if (*caret > 0) (*caret)--;   return 0;
+            case UI_SCAN_RIGHT: if (*caret < len) (*caret)++; return 0;
+            case UI_SCAN_HOME:  *caret = 0;                   return 0;
+            case UI_SCAN_END:   *caret = len;                 return 0;
+        }
+    }
+
+    if (key->code == '\b' || key->code == 127) {
+        if (*caret == 0) return 0;
+        for (uint32_t i = *caret - 1; i < len; i++) buf[i] = buf[i + 1];
+        (*caret)--;
+        return 1;
+    }
+    if (key->ch < 0x20 || key->ch > 0x7E || len + 1 >= cap) return 0;
+    for (uint32_t i = len + 1; i > *caret; i--) buf[i] = buf[i - 1];
+    buf[(*caret)++] = key->ch;
+    return 1;
+}
+
+// A single-line text field editing `buf` (capacity `cap` including the NUL)
+// with the caret at *caret. While focused, a pressed key is applied; the
+// caller decides when the caret is drawn, so it can blink. The text scrolls
+// left to keep the caret in view. Returns 1 if the text changed.
+int ui_text_field_themed(const ui_theme_t *t, rect_t r, char *buf, uint32_t cap, uint32_t *caret,
+                         int focused, int show_caret, const ui_key_t *key) {
+    if (!t) t = ui_theme();
+    if (!buf || !caret || cap == 0) return 0;
+    int changed = focused && key && key->pressed && edit_text(buf, cap, caret, key);
+    uint32_t len = (uint32_t)strlen(buf);
+    if (*caret > len) *caret = len;
+
+    mark_dirty_rect(r);
+    draw_rect(r.x, r.y, r.w, r.h, t->field);
+    if (r.w >= 2 && r.h >= 2)
+        draw_rect_outline(r.x, r.y, r.w, r.h, 1, focused ? t->accent : t->border);
+    if (r.w <= 2 * UI_PAD || r.h <= 2 * UI_PAD) return changed;
+
+    rect_t inner = { r.x + UI_PAD, r.y + UI_PAD, r.w - 2 * UI_PAD, r.h - 2 * UI_PAD };
+    uint32_t cols  = inner.w / FONT_WIDTH;
+    uint32_t first = *caret >= cols ? *caret - cols + 1 : 0;
+    uint32_t ty    = inner.y + (inner.h > FONT_HEIGHT ? (inner.h - FONT_HEIGHT) / 2 : 0);
+
+    if (push_clip_rect(inner) != OK) return changed;
+    draw_text(inner.x, ty, buf + first, t->text);
+    if (focused && show_caret)
+        draw_rect(inner.x + (*caret - first) * FONT_WIDTH, ty, 1, FONT_HEIGHT, t->text);
+    pop_clip();
+    return changed;
+}
+
+int ui_text_field(rect_t r, char *buf, uint32_t cap, uint32_t *caret,
+                  int focused, int show_caret, const ui_key_t *key) {
+    return ui_text_field_themed(ui_theme(), r, buf, cap, caret, focused, show_caret, key);
+}
+
+// A vertical list of rows, the selected one highlighted in the accent colour
+// and the hovered one lightened. Rows past the bottom of r are cut off.
+// Clicking a row selects it; returns its index, or -1 if nothing was clicked.
+int32_t ui_list_themed(const ui_theme_t *t, rect_t r, const char *const *items, uint32_t count,
+                       uint32_t *selected, const ui_mouse_t *mouse) {
+    if (!t) t = ui_theme();
+    if (!items || rect_is_empty(r)) return -1;
+    if (push_clip_rect(r) != OK) return -1;
+
+    int32_t clicked = -1;
+    mark_dirty_rect(r);
+    draw_rect(r.x, r.y, r.w, r.h, t->field);
+    for (uint32_t i = 0; i < count; i++) {
+        uint64_t top = (uint64_t)r.y + (uint64_t)i * UI_ROW_H;
+        if (top >= (uint64_t)r.y + r.h) break;
+
+        rect_t row = { r.x, (uint32_t)top, r.w, UI_ROW_H }, hit;
+        if (!rect_intersect(row, r, &hit)) break;
+        int held;
+        if (interact(hit, mouse, &held)) {
+            clicked = (int32_t)i;
+            if (selected) *selected = i;
+        }
+
+        int is_sel = selected && *selected == i;
+        uint32_t bg = is_sel ? t->accent : t->field;
+        if (ui_mouse_over(mouse, hit)) bg = is_sel ? lighten(bg, 24) : t->highlight;
+        draw_rect(row.x, row.y, row.w, row.h, bg);
+        if (items[i]) draw_text(row.x + UI_PAD, row.y + UI_PAD, items[i], t->text);
+    }
+    pop_clip();
+    return clicked;
+}
+
+int32_t ui_list(rect_t r, const char *const *items, uint32_t count,
+                uint32_t *selected, const ui_mouse_t *mouse) {
+    return ui_list_themed(ui_theme(), r, items, count, selected, mouse);
+}
+
+// Window chrome: a one-pixel border and a gradient title bar holding the
+// title and a close button. Inactive windows are drawn in muted colours.
+// Returns the area left for content and the close button's rectangle; both
+// are empty if r is too small to hold the frame.
+ui_window_t ui_window_frame_themed(const ui_theme_t *t, rect_t r, const char *title, int focused) {
+    if (!t) t = ui_theme();
+    ui_window_t out = { { 0, 0, 0, 0 }, { 0, 0, 0, 0 } };
+    if (r.w < UI_TITLE_H + 2 || r.h < UI_TITLE_H + 1) return out;
+
+    uint32_t top    = focused ? lighten(t->accent, 40) : t->face_hover;
+    uint32_t bottom = focused ? t->accent : t->face;
+    mark_dirty_rect(r);
+    draw_rect_outline(r.x, r.y, r.w, r.h, 1, focused ? t->accent : t->border);
+    draw_gradient_vertical(r.x + 1, r.y + 1, r.w - 2, UI_TITLE_H - 1, top, bottom);
+
+    uint32_t bs = UI_TITLE_H - 2 * UI_PAD;
+    out.close   = (rect_t){ r.x + r.w - 1 - UI_PAD - bs, r.y + UI_PAD, bs, bs };
+    out.content = (rect_t){ r.x + 1, r.y + UI_TITLE_H, r.w - 2, r.h - UI_TITLE_H - 1 };
+    draw_rect(out.content.x, out.content.y, out.content.w, out.content.h, t->background);
+
+    fill_rounded_rect(out.close.x, out.close.y, bs, bs, 2, focused ? t->close : t->face_down);
+    uint32_t x0 = out.close.x + 3, y0 = out.close.y + 3;
+    uint32_t x1 = out.close.x + bs - 4, y1 = out.close.y + bs - 4;
+    draw_line(x0, y0, x1, y1, t->text);
+    draw_line(x0, y1, x1, y0, t->text);
+
+    if (title) {
+        rect_t bar = { r.x + 1 + UI_PAD, r.y + 1, out.close.x - r.x - 2 * UI_PAD - 1, UI_TITLE_H - 1 };
+        if (push_clip_rect(bar) == OK) {
+            draw_text(bar.x, r.y + 1 + UI_PAD, title, focused ? t->text : t->border);
+            pop_clip();
+        }
+    }
+    return out;
+}
+
+ui_window_t ui_window_frame(rect_t r, const char *title, int focused) {
+    return ui_window_frame_themed(ui_theme(), r, title, focused);
+}
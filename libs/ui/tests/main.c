@@ -0,0 +1,15 @@
+#include "check.h"
+
+void theme_tests(void);
+void text_field_tests(void);
+void widget_tests(void);
+
+static void (*const suites[])(void) = {
+    theme_tests,
+    text_field_tests,
+    widget_tests,
+};
+
+int main(void) {
+    return run_suites("ui", suites, sizeof(suites) / sizeof(suites[0]));
+}
@@ -0,0 +1,59 @@
+#include "check.h"
+#include <string.h>
+#include <ui.h>
+
+static const rect_t field = { 0, 0, 30, 14 };
+
+static int press(char *buf, uint32_t cap, uint32_t *caret, char ch, uint8_t scancode) {
+    ui_key_t key = { (uint8_t)ch, ch, 1, scancode };
+    return ui_text_field(field, buf, cap, caret, 1, 1, &key);
+}
+
+// Typing appends at the caret and moves it along; backspace takes the
+// character before it back out.
+static void test_typing(void) {
+    reset();
+    char buf[8] = "ab";
+    uint32_t caret = 2;
+    CHECK(press(buf, sizeof(buf), &caret, 'c', 0) == 1);
+    CHECK(strcmp(buf, "abc") == 0);
+    CHECK(caret == 3);
+    CHECK(press(buf, sizeof(buf), &caret, '\b', 0) == 1);
+    CHECK(strcmp(buf, "ab") == 0);
+    CHECK(caret == 2);
+
+    // A full buffer takes no more characters.
+    char full[3] = "xy";
+    uint32_t end = 2;
+    CHECK(press(full, sizeof(full), &end, 'z', 0) == 0);
+    CHECK(strcmp(full, "xy") == 0);
+}
+
+// The arrow, Home and End keys move the caret within the text without
+// changing it, and typing then inserts at the new position.
+static void test_caret_keys(void) {
+    reset();
+    char buf[8] = "abcd";
+    uint32_t caret = 4;
+    CHECK(press(buf, sizeof(buf), &caret, 0, UI_SCAN_LEFT) == 0);
+    CHECK(caret == 3);
+    CHECK(press(buf, sizeof(buf), &caret, 0, UI_SCAN_HOME) == 0);
+    CHECK(caret == 0);
+    CHECK(press(buf, sizeof(buf), &caret, 0, UI_SCAN_LEFT) == 0);
+    CHECK(caret == 0);
+    CHECK(press(buf, sizeof(buf), &caret, 0, UI_SCAN_RIGHT) == 0);
+    CHECK(caret == 1);
+    CHECK(press(buf, sizeof(buf), &caret, 'X', 0) == 1);
+    CHECK(strcmp(buf, "aXbcd") == 0);
+    CHECK(caret == 2);
+    CHECK(press(buf, sizeof(buf), &caret, 0, UI_SCAN_END) == 0);
+    CHECK(caret == 5);
+    CHECK(press(buf, sizeof(buf), &caret, 0, UI_SCAN_RIGHT) == 0);
+    CHECK(caret == 5);
+    CHECK(strcmp(buf, "aXbcd") == 0);
+}
+
+void text_field_tests(void) {
+    test_typing();
+    test_caret_keys();
+}
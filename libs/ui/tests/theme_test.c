@@ -0,0 +1,40 @@
+#include "check.h"
+#include <ui.h>
+
+static const ui_mouse_t away = { -1, -1, 0, 0, 0 };
+
+// A button fills its face with the theme's face colour, so switching the
+// default theme changes what the plain ui_button() draws.
+static void test_button_follows_theme(void) {
+    reset();
+    rect_t r = { 2, 2, 20, 14 };
+    ui_theme_t dark = ui_theme_dark(), light = ui_theme_light();
+
+    ui_set_theme(&dark);
+    ui_button(r, "", &away);
+    CHECK(read_pixel(r.x + 4, r.y + 4) == dark.face);
+
+    ui_set_theme(&light);
+    ui_button(r, "", &away);
+    CHECK(read_pixel(r.x + 4, r.y + 4) == light.face);
+
+    CHECK(ui_button_themed(&dark, r, "", &away) == 0);
+    CHECK(read_pixel(r.x + 4, r.y + 4) == dark.face);
+    ui_set_theme(&dark);
+}
+
+// The themed progress bar fills with the accent over the track.
+static void test_progress_bar_themed(void) {
+    reset();
+    ui_theme_t t = ui_theme_light();
+    ui_progress_bar_themed(&t, (rect_t){ 0, 0, 10, 4 }, 128, 0);
+    CHECK(read_pixel(0, 0) == t.accent);
+    CHECK(read_pixel(4, 3) == t.accent);
+    CHECK(read_pixel(5, 0) == t.track);
+    CHECK(read_pixel(9, 3) == t.track);
+}
+
+void theme_tests(void) {
+    test_button_follows_theme();
+    test_progress_bar_themed();
+}
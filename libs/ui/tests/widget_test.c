@@ -0,0 +1,250 @@
+#include "check.h"
+#include <ui.h>
+
+// List rows are a line of text with 3 pixels of padding above and below.
+#define ROW_H (FONT_HEIGHT + 6)
+// The title bar is a list row plus two pixels, border included.
+#define TITLE_H (ROW_H + 2)
+
+// Press and release inside the button is one click, reported on the release
+// frame only; the face shows the press while the button is held. Letting go
+// outside is not a click.
+static void test_button_click(void) {
+    reset();
+    ui_theme_t t = ui_theme_dark();
+    ui_set_theme(&t);
+    rect_t r = { 4, 4, 16, 10 };
+    ui_mouse_t m = { 0, 0, 0, 0, 0 };
+
+    ui_mouse_update(&m, 8, 8, 0);
+    CHECK(ui_button(r, "", &m) == 0);
+    CHECK(read_pixel(6, 6) == t.face_hover);
+    ui_mouse_update(&m, 8, 8, 1);
+    CHECK(ui_button(r, "", &m) == 0);
+    CHECK(read_pixel(6, 6) == t.face_down);
+    ui_mouse_update(&m, 9, 8, 0);
+    CHECK(ui_button(r, "", &m) == 1);
+    ui_mouse_update(&m, 9, 8, 0);
+    CHECK(ui_button(r, "", &m) == 0);
+
+    ui_mouse_update(&m, 8, 8, 1);
+    CHECK(ui_button(r, "", &m) == 0);
+    ui_mouse_update(&m, 30, 30, 0);
+    CHECK(ui_button(r, "", &m) == 0);
+}
+
+// A click flips the check box and reports the change on the release frame;
+// a second click flips it back, and frames without a click change nothing.
+static void test_checkbox_click(void) {
+    reset();
+    rect_t r = { 2, 2, 12, 12 };
+    ui_mouse_t m = { 0, 0, 0, 0, 0 };
+    int checked = 0;
+
+    for (int round = 0; round < 2; round++) {
+        ui_mouse_update(&m, 6, 6, 1);
+        CHECK(ui_checkbox(r, &checked, &m) == 0);
+        CHECK(checked == round);
+        ui_mouse_update(&m, 6, 6, 0);
+        CHECK(ui_checkbox(r, &checked, &m) == 1);
+        CHECK(checked == !round);
+    }
+    ui_mouse_update(&m, 6, 6, 0);
+    CHECK(ui_checkbox(r, &checked, &m) == 0);
+    CHECK(checked == 0);
+}
+
+// Grabbing the slider at its left end sets 0; dragging past the right edge,
+// off the widget, clamps to 255. Once released the mouse no longer moves it.
+static void test_slider_drag(void) {
+    reset();
+    rect_t r = { 2, 10, 24, 8 };
+    ui_mouse_t m = { 0, 0, 0, 0, 0 };
+    uint8_t value = 100;
+
+    ui_mouse_update(&m, 2, 14, 1);
+    CHECK(ui_slider(r, &value, &m) == 1);
+    CHECK(value == 0);
+    ui_mouse_update(&m, 14, 14, 1);
+    CHECK(ui_slider(r, &value, &m) == 1);
+    CHECK(value > 0 && value < 255);
+    ui_mouse_update(&m, 200, 30, 1);
+    CHECK(ui_slider(r, &value, &m) == 1);
+    CHECK(value == 255);
+    ui_mouse_update(&m, 200, 30, 1);
+    CHECK(ui_slider(r, &value, &m) == 0);
+
+    ui_mouse_update(&m, 200, 30, 0);
+    ui_slider(r, &value, &m);
+    ui_mouse_update(&m, 5, 14, 0);
+    CHECK(ui_slider(r, &value, &m) == 0);
+    CHECK(value == 255);
+}
+
+static uint32_t count_row(uint32_t y, uint32_t c) {
+    uint32_t n = 0;
+    for (uint32_t x = 0; x < TEST_W; x++)
+        if (read_pixel(x, y) == c) n++;
+    return n;
+}
+
+// At 128/255 half of a 30-pixel track is filled; 0 fills nothing and 255
+// reaches the last column without spilling past it.
+static void test_progress_half(void) {
+    rect_t r = { 1, 4, 30, 6 };
+    reset();
+    ui_progress_bar(r, 128, COLOR_RED, COLOR_BLUE, 0);
+    CHECK(count_row(6, COLOR_RED) == 15);
+    CHECK(count_row(6, COLOR_BLUE) == 15);
+    CHECK(read_pixel(1, 6) == COLOR_RED && read_pixel(30, 6) == COLOR_BLUE);
+
+    ui_progress_bar(r, 0, COLOR_RED, COLOR_BLUE, 0);
+    CHECK(count_row(6, COLOR_RED) == 0);
+    ui_progress_bar(r, 255, COLOR_RED, COLOR_BLUE, 0);
+    CHECK(count_row(6, COLOR_RED) == 30);
+    CHECK(read_pixel(31, 6) == 0);
+
+    reset();
+    ui_progress_bar(r, 128, COLOR_RED, COLOR_BLUE, 1);
+    uint32_t red = count_row(7, COLOR_RED);
+    CHECK(red >= 13 && red <= 15);
+}
+
+// Clicking in the second row selects it and returns its index on the
+// release frame, and it is then drawn highlighted. Rows running past the
+// bottom of the list are cut off there.
+static void test_list_click(void) {
+    static const char *const items[] = { "a", "b", "c", "d" };
+    reset();
+    ui_theme_t t = ui_theme_dark();
+    ui_set_theme(&t);
+    rect_t r = { 2, 1, 24, 2 * ROW_H + 2 };
+    ui_mouse_t m = { 0, 0, 0, 0, 0 };
+    uint32_t selected = 0;
+
+    ui_mouse_update(&m, 10, (int32_t)(r.y + ROW_H + 2), 1);
+    CHECK(ui_list(r, items, 4, &selected, &m) == -1);
+    ui_mouse_update(&m, 10, (int32_t)(r.y + ROW_H + 2), 0);
+    CHECK(ui_list(r, items, 4, &selected, &m) == 1);
+    CHECK(selected == 1);
+    ui_mouse_update(&m, 30, 30, 0);
+    CHECK(ui_list(r, items, 4, &selected, &m) == -1);
+    CHECK(read_pixel(r.x + 1, r.y + ROW_H + 1) == t.accent);
+    CHECK(read_pixel(r.x + 1, r.y + r.h) == 0);
+}
+
+// The content area sits inside the one-pixel border, directly below the
+// title bar, with the close button up in the bar. A frame too small for its
+// title bar returns empty rectangles.
+static void test_window_content_rect(void) {
+    reset();
+    ui_theme_t t = ui_theme_dark();
+    ui_set_theme(&t);
+    rect_t r = { 1, 2, 30, 28 };
+    ui_window_t w = ui_window_frame(r, "x", 1);
+    CHECK(w.content.x == r.x + 1 && w.content.w == r.w - 2);
+    CHECK(w.content.y == r.y + TITLE_H);
+    CHECK(w.content.y + w.content.h == r.y + r.h - 1);
+    CHECK(w.close.y + w.close.h <= w.content.y);
+    CHECK(w.close.x + w.close.w < r.x + r.w);
+    CHECK(read_pixel(w.content.x, w.content.y) == t.background);
+    CHECK(read_pixel(w.content.x, w.content.y - 1) != t.background);
+
+    w = ui_window_frame((rect_t){ 0, 0, 30, TITLE_H }, "x", 1);
+    CHECK(rect_is_empty(w.content) && rect_is_empty(w.close));
+}
+
+// ui_mouse_over() counts the top-left edge of a rectangle as inside and the
+// bottom-right edge as outside; a pointer off the screen is over nothing.
+// ui_mouse_update() derives the press and release edges.
+static void test_mouse_state(void) {
+    rect_t r = { 4, 6, 10, 5 };
+    ui_mouse_t m = { 4, 6, 0, 0, 0 };
+    CHECK(ui_mouse_over(&m, r));
+    m.x = 13, m.y = 10;
+    CHECK(ui_mouse_over(&m, r));
+    m.x = 14;
+    CHECK(!ui_mouse_over(&m, r));
+    m.x = 8, m.y = 11;
+    CHECK(!ui_mouse_over(&m, r));
+    m.x = -1, m.y = 7;
+    CHECK(!ui_mouse_over(&m, (rect_t){ 0, 0, 10, 10 }));
+    CHECK(!ui_mouse_over(0, r));
+
+    ui_mouse_update(&m, 5, 7, 1);
+    CHECK(m.left_down && m.left_pressed && !m.left_released);
+    ui_mouse_update(&m, 6, 7, 1);
+    CHECK(m.left_down && !m.left_pressed && !m.left_released);
+    ui_mouse_update(&m, 6, 8, 0);
+    CHECK(!m.left_down && !m.left_pressed && m.left_released);
+    CHECK(m.x == 6 && m.y == 8);
+}
+
+// A frame that draws one button presents exactly the button's rectangle,
+// and the next frame, with nothing drawn, presents nothing.
+static void test_button_dirty_rect(void) {
+    reset();
+    CHECK(init_back_buffer() == OK);
+    enable_dirty_tracking(1);
+    rect_t r = { 5, 7, 12, 9 };
+    ui_mouse_t m = { -1, -1, 0, 0, 0 };
+
+    ui_begin_frame();
+    ui_button(r, "ok", &m);
+    for (uint32_t i = 0; i < TEST_W * TEST_H; i++) test_fb[i] = 0xDEADBEEF;
+    CHECK(ui_end_frame() == OK);
+    uint32_t inside = 0, outside = 0;
+    for (uint32_t y = 0; y < TEST_H; y++)
+        for (uint32_t x = 0; x < TEST_W; x++) {
+            if (test_fb[y * TEST_W + x] == 0xDEADBEEF) continue;
+            if (rect_contains(r, x, y)) inside++;
+            else outside++;
+        }
+    CHECK(inside == r.w * r.h);
+    CHECK(outside == 0);
+
+    ui_begin_frame();
+    CHECK(ui_end_frame() == OK);
+    CHECK(count_color(0xDEADBEEF) == TEST_W * TEST_H - r.w * r.h);
+    enable_dirty_tracking(0);
+}
+
+// Dragging the thumb past the bottom of the track scrolls to the end of the
+// content, content_h - view_h, and no further; dragging back above the top
+// returns to 0. A scroll already out of range is clamped.
+static void test_scrollbar_drag(void) {
+    reset();
+    rect_t r = { 24, 0, 6, 30 };
+    ui_mouse_t m = { 0, 0, 0, 0, 0 };
+    uint32_t scroll = 0;
+
+    ui_mouse_update(&m, 26, 2, 1);
+    CHECK(ui_scrollbar_v(r, 100, 30, &scroll, &m) == 0);
+    ui_mouse_update(&m, 26, 200, 1);
+    CHECK(ui_scrollbar_v(r, 100, 30, &scroll, &m) == 1);
+    CHECK(scroll == 100 - 30);
+    ui_mouse_update(&m, 26, 300, 1);
+    CHECK(ui_scrollbar_v(r, 100, 30, &scroll, &m) == 0);
+    CHECK(scroll == 100 - 30);
+    ui_mouse_update(&m, 26, -50, 1);
+    CHECK(ui_scrollbar_v(r, 100, 30, &scroll, &m) == 1);
+    CHECK(scroll == 0);
+    ui_mouse_update(&m, 26, -50, 0);
+    ui_scrollbar_v(r, 100, 30, &scroll, &m);
+
+    scroll = 500;
+    CHECK(ui_scrollbar_v(r, 100, 30, &scroll, &m) == 1);
+    CHECK(scroll == 100 - 30);
+}
+
+void widget_tests(void) {
+    test_button_click();
+    test_checkbox_click();
+    test_slider_drag();
+    test_progress_half();
+    test_list_click();
+    test_window_content_rect();
+    test_mouse_state();
+    test_button_dirty_rect();
+    test_scrollbar_drag();
+}
@@ -64,7 +64,8 @@ int main() {
     uint64_t height = get_fb_height();
     uint64_t pitch  = get_fb_pitch();
 
-    init_display();
+    fb_error err = init_display();
+    if (err != OK) { printf("init_display failed: %s\n", fb_error_str(err)); while (1); }
 
     context_t *ctx = context_new(fb, width, height, pitch);
     if (!ctx) { printf("context_new failed\n"); while (1); }